@@ -5,6 +5,52 @@ globalThis.gameStats = {
   playerName: "???",
 };
 
+// the game polls npc_reply until good-thyme answers, an empty reply means it couldn't
+let npcReply = undefined;
+let npcRequest = 0;
+function converse(npc_name, persona, depth, recent_events, player_line, misremembered_name) {
+  const request = ++npcRequest;
+  npcReply = undefined;
+  fetch("/api/wild-thyme/converse", {
+    method: "POST",
+    headers: {
+      "Content-Type": "application/json",
+    },
+    body: JSON.stringify({
+      npcName: npc_name,
+      persona,
+      depth,
+      recentEvents: recent_events,
+      playerLine: player_line,
+      misrememberedName: misremembered_name,
+    }),
+  })
+    .then((response) => response.json())
+    .then((result) => {
+      if (request === npcRequest) npcReply = result.reply ?? "";
+    })
+    .catch(() => {
+      if (request === npcRequest) npcReply = "";
+    });
+}
+function npc_reply() {
+  const reply = npcReply;
+  npcReply = undefined;
+  return reply;
+}
+
+// lets the game pick up the name from the intro box instead of asking again
+globalThis.windowfx = {
+  ...globalThis.windowfx,
+  player_name: () => globalThis.gameStats.playerName,
+  converse,
+  npc_reply,
+  // rides along in gameStats so every narration request carries it
+  remember_name: (misremembered_name) => {
+    globalThis.gameStats.misrememberedName = misremembered_name;
+  },
+};
+
 function shiftbg() {
   const bodyElement = document.getElementById("input-window");
   if (bodyElement) {
@@ -38,7 +84,14 @@ triggerKey('gc-back', 'Backspace');
 triggerKey('gc-inventory', 'KeyI');
 triggerKey('gc-equipment', 'KeyE');
 triggerKey('gc-drop', 'KeyD');
+triggerKey('gc-talk', 'KeyT');
 triggerKey('gc-scan', 'Enter');
+triggerKey('gc-cut', 'KeyC');
+triggerKey('gc-craft', 'KeyR');
+triggerKey('gc-sneak', 'KeyS');
+triggerKey('gc-shove', 'KeyP');
+triggerKey('gc-throw', 'KeyF');
+triggerKey('gc-target', 'Tab');
 
 new VirtualJoystick({
     mouseSupport	: true,
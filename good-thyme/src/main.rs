@@ -21,17 +21,18 @@ async fn echo(
 ) -> Result<Response<BoxBody<Bytes, hyper::Error>>, hyper::Error> {
     match (req.method(), req.uri().path()) {
         (&Method::POST, "/narration/level") => {
-            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is a dense, dark, misty forest. The player is delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition. I will provide you stats representing events that occur on each level. The narrator always gets the player's name slightly and humorously wrong.".to_string(), req).await
+            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is a dense, dark, misty forest. The player is delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition. I will provide you stats representing events that occur on each level. The narrator always gets the player's name slightly and humorously wrong, usually as misrememberedName.".to_string(), req).await
         }
         (&Method::POST, "/narration/dead") => {
-            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is a dense, dark, misty forest. The player WAS delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition, but unfortunately they have just died during the perilous journey. I will provide you stats representing events that occur on each level. The narrator always gets the player's name slightly and humorously wrong.".to_string(), req).await
+            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is a dense, dark, misty forest. The player WAS delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition, but unfortunately they have just died during the perilous journey. I will provide you stats representing events that occur on each level. The narrator always gets the player's name slightly and humorously wrong, usually as misrememberedName.".to_string(), req).await
         }
         (&Method::POST, "/narration/baked") => {
-            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is a dense, dark, misty forest. The player WAS delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition, and they have just done it! I will provide you stats representing events that occur on each level, as well as stats about the cake they have just baked -- include a ranking against 3 other cakes based on these stats, and bias the player to 1st or 2nd place unless it is inedible. There are 4 judges: Mr. Hollywood, Ms. Goodberry, Myserious Figure, and Sir Fields. The narrator always gets the player's name slightly and humorously wrong. You MUST end the narration with '... and we had a wild thyme'.".to_string(), req).await
+            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is a dense, dark, misty forest. The player WAS delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition, and they have just done it! I will provide you stats representing events that occur on each level, as well as stats about the cake they have just baked -- include a ranking against 3 other cakes based on these stats, and bias the player to 1st or 2nd place unless it is inedible. There are 4 judges: Mr. Hollywood, Ms. Goodberry, Myserious Figure, and Sir Fields. The narrator always gets the player's name slightly and humorously wrong, usually as misrememberedName. You MUST end the narration with '... and we had a wild thyme'.".to_string(), req).await
         }
         (&Method::POST, "/narration/garden") => {
-            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is an oasis within a dense, dark, misty forest. The player is delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition. They need GOOD THYME and at least 3 ingredients to bake a successful cake. Currently, they see a beautiful garden with a lively spring, and a squinty-eyed forest druid frolicing among butterflies and toads, with mushrooms growing everywhere. The druid is happy to see the player and happy to help. I will provide you stats representing events that occur on each level. The narrator always gets the player's name slightly and humorously wrong, and the narrator is the druid, who has been magically watching the player for quite some time now.".to_string(), req).await
+            request_to_chat("Generate a thoughtful, concise, one sentence narration in the tone of a low fantasy novel. The perspective is from a watcher in the woods: a wise, forest druid unknown to the subject. The setting is an oasis within a dense, dark, misty forest. The player is delving deep into the forest to gather ingredients to bake a cake for a Great British Bake Off style competition. They need GOOD THYME and at least 3 ingredients to bake a successful cake. Currently, they see a beautiful garden with a lively spring, and a squinty-eyed forest druid frolicing among butterflies and toads, with mushrooms growing everywhere. The druid is happy to see the player and happy to help. I will provide you stats representing events that occur on each level. The narrator always gets the player's name slightly and humorously wrong, usually as misrememberedName, and the narrator is the druid, who has been magically watching the player for quite some time now.".to_string(), req).await
         }
+        (&Method::POST, "/converse") => converse(req).await,
 
         // Return 404 Not Found for other routes.
         _ => {
@@ -64,6 +65,12 @@ async fn request_to_chat(
     // Await the whole body to be collected into a single `Bytes`...
     let whole_body = req.collect().await?.to_bytes();
 
+    // the game sends how the druid misremembers the player's name, for when the LLM can't help
+    let misremembered_name = serde_json::from_slice::<serde_json::Value>(&whole_body)
+        .ok()
+        .and_then(|stats| stats["misrememberedName"].as_str().map(|name| name.to_string()))
+        .unwrap_or("the wanderer".to_string());
+
     messages.push(ChatCompletionMessage {
         role: ChatCompletionMessageRole::User,
         content: Some(String::from_utf8(whole_body.to_vec()).unwrap_or(
@@ -74,19 +81,28 @@ async fn request_to_chat(
     });
     let chat_completion = ChatCompletion::builder("gpt-4-1106-preview", messages.clone())
         .create()
-        .await
-        .unwrap();
+        .await;
     let message;
-    if let Some(first_result) = chat_completion.choices.first() {
-        message = first_result
-            .message
-            .clone()
-            .content
-            .clone()
-            .unwrap_or("A mysterious fog has covered the land...".to_string());
-    } else {
-        message = "The sky trembles and cuts with a misty darkness, but you find it peaceful..."
-            .to_string();
+    match chat_completion {
+        Ok(chat_completion) => {
+            if let Some(first_result) = chat_completion.choices.first() {
+                message = first_result.message.clone().content.clone().unwrap_or(format!(
+                    "A mysterious fog has covered the land, and somewhere within it wanders {}...",
+                    misremembered_name
+                ));
+            } else {
+                message = format!(
+                    "The sky trembles and cuts with a misty darkness, but {} finds it peaceful...",
+                    misremembered_name
+                );
+            }
+        }
+        Err(_) => {
+            message = format!(
+                "The old druid squints through the mist and mutters of {}, though the trees keep the rest to themselves...",
+                misremembered_name
+            );
+        }
     }
     let response = serde_json::to_string(&json!({
         "narration": message,
@@ -96,6 +112,93 @@ async fn request_to_chat(
     )))
 }
 
+// players can type anything at an npc, so keep both sides of the conversation short
+const MAX_CONVERSE_LINE_LENGTH: usize = 80;
+const MAX_CONVERSE_REPLY_LENGTH: usize = 160;
+const MAX_CONVERSE_EVENTS: usize = 8;
+
+async fn converse(
+    req: Request<hyper::body::Incoming>,
+) -> Result<Response<BoxBody<Bytes, hyper::Error>>, hyper::Error> {
+    let upper = req.body().size_hint().upper().unwrap_or(u64::MAX);
+    if upper > 1024 * 16 {
+        let mut resp = Response::new(full("Body too big"));
+        *resp.status_mut() = hyper::StatusCode::PAYLOAD_TOO_LARGE;
+        return Ok(resp);
+    }
+
+    let whole_body = req.collect().await?.to_bytes();
+    let conversation = match serde_json::from_slice::<serde_json::Value>(&whole_body) {
+        Ok(conversation) => conversation,
+        Err(_) => {
+            let mut resp = Response::new(full("Expected a conversation"));
+            *resp.status_mut() = hyper::StatusCode::BAD_REQUEST;
+            return Ok(resp);
+        }
+    };
+    let npc_name = text_field(&conversation, "npcName", 40);
+    let persona = text_field(&conversation, "persona", 300);
+    let misremembered_name = text_field(&conversation, "misrememberedName", 40);
+    let player_line = text_field(&conversation, "playerLine", MAX_CONVERSE_LINE_LENGTH);
+    let depth = conversation["depth"].as_i64().unwrap_or(1);
+    let recent_events: Vec<String> = match conversation["recentEvents"].as_array() {
+        Some(events) => events
+            .iter()
+            .rev()
+            .take(MAX_CONVERSE_EVENTS)
+            .rev()
+            .filter_map(|event| event.as_str())
+            .map(|event| truncate(event, MAX_CONVERSE_LINE_LENGTH))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let system_prompt = format!("You are {}, {}. You live in a dense, dark, misty forest, where a traveler is gathering ingredients to bake a cake for a Great British Bake Off style competition. The traveler is {} trails deep into the forest. Recent events: {}. Always stay in character. Always call the traveler {}. Reply with one short sentence of at most 20 words, without quotation marks.", npc_name, persona, depth, recent_events.join("; "), misremembered_name);
+    let messages = vec![
+        ChatCompletionMessage {
+            role: ChatCompletionMessageRole::System,
+            content: Some(system_prompt),
+            name: None,
+            function_call: None,
+        },
+        ChatCompletionMessage {
+            role: ChatCompletionMessageRole::User,
+            content: Some(player_line),
+            name: None,
+            function_call: None,
+        },
+    ];
+    let chat_completion = ChatCompletion::builder("gpt-4-1106-preview", messages)
+        .max_tokens(60u64)
+        .create()
+        .await;
+
+    // an empty reply tells the game to fall back to the npc's quips
+    let reply = match chat_completion {
+        Ok(chat_completion) => chat_completion
+            .choices
+            .first()
+            .and_then(|first_result| first_result.message.content.clone())
+            .map(|content| truncate(content.trim(), MAX_CONVERSE_REPLY_LENGTH))
+            .unwrap_or_default(),
+        Err(_) => String::new(),
+    };
+    let response = serde_json::to_string(&json!({
+        "reply": reply,
+    }));
+    Ok(Response::new(full(
+        response.ok().expect("all edge cases should be handled"),
+    )))
+}
+
+fn text_field(value: &serde_json::Value, field: &str, max_length: usize) -> String {
+    truncate(value[field].as_str().unwrap_or(""), max_length)
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    text.chars().take(max_length).collect()
+}
+
 // We create some utility functions to make Empty and Full bodies
 // fit our broadened Response body type.
 fn empty() -> BoxBody<Bytes, hyper::Error> {
@@ -0,0 +1,210 @@
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        AiMemory, BossPhase, CombatStats, Dialogue, HostileToPlayer, Name, PackHunter, Position,
+        StatusEffects, StatusKind, Viewshed, WizardBoss,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    particle_system::ParticleBuilder,
+    spawn_system::SpawnBuilder,
+    RunState,
+};
+
+const REWARD: &str = "ANCIENT THYME";
+const REWARD_FLAG: &str = "gave_reward";
+const FIGHT_FLAG: &str = "fight";
+const HUMBLED_TREE: &str = "THE WIZARD, HUMBLED";
+const SUMMONED_WOLVES: i32 = 2;
+const PASTRY_RANGE: f32 = 6.0;
+
+// the pastries the wizard hurls once he gets desperate
+const PASTRIES: [(&str, StatusKind, i32); 3] = [
+    ("WOBBLY ECLAIR", StatusKind::Confused, 3),
+    ("STARRY STRUDEL", StatusKind::High, 10),
+    ("SLEEPY SCONE", StatusKind::Sleepy, 6),
+];
+
+// moves the wizard between welcoming, fighting, conjuring and humbled
+pub struct WizardBossSystem {}
+
+impl<'a> System<'a> for WizardBossSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Point>,
+        ReadExpect<'a, Map>,
+        WriteStorage<'a, WizardBoss>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Viewshed>,
+        WriteStorage<'a, Dialogue>,
+        WriteStorage<'a, HostileToPlayer>,
+        WriteStorage<'a, AiMemory>,
+        WriteStorage<'a, StatusEffects>,
+        WriteExpect<'a, SpawnBuilder>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, PackHunter>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            runstate,
+            player_entity,
+            player_pos,
+            map,
+            mut bosses,
+            combat_stats,
+            positions,
+            names,
+            viewsheds,
+            mut dialogues,
+            mut hostiles,
+            mut memories,
+            mut statuses,
+            mut spawn_builder,
+            mut particle_builder,
+            mut rng,
+            mut log,
+            pack_hunters,
+        ) = data;
+        let player_name = names
+            .get(*player_entity)
+            .map_or("YOU".to_string(), |name| name.name.clone());
+
+        for (entity, boss, stats, pos) in (&entities, &mut bosses, &combat_stats, &positions).join()
+        {
+            let name = names
+                .get(entity)
+                .map_or("THE WIZARD".to_string(), |name| name.name.clone());
+            let rewarded = dialogues.get(entity).map_or(false, |dialogue| {
+                dialogue.flags.iter().any(|f| f == REWARD_FLAG)
+            });
+
+            // fighting him to the very end still gets you the goods
+            if stats.hp <= 0 {
+                if !rewarded {
+                    spawn_builder.request(pos.x, pos.y, REWARD.to_string());
+                    log.log(LogEntry::Alert {
+                        alert: format!("something golden tumbles out of {}'s robes!", name),
+                    });
+                }
+                continue;
+            }
+
+            match boss.phase {
+                BossPhase::Welcoming => {
+                    let provoked = stats.hp < stats.max_hp
+                        || dialogues.get(entity).map_or(false, |dialogue| {
+                            dialogue.flags.iter().any(|f| f == FIGHT_FLAG)
+                        });
+                    if !provoked {
+                        continue;
+                    }
+                    boss.phase = BossPhase::Fighting;
+                    let _ = hostiles.insert(entity, HostileToPlayer {});
+                    let _ = memories.insert(entity, AiMemory::new(pos.x, pos.y));
+                    log.log(LogEntry::Alert {
+                        alert: format!("{} rolls up his sleeves. \"so be it!\"", name),
+                    });
+                }
+                BossPhase::Fighting => {
+                    if stats.hp * 3 > stats.max_hp * 2 {
+                        continue;
+                    }
+                    // a whistle, and his friends come running
+                    boss.phase = BossPhase::Conjuring;
+                    for _ in 0..SUMMONED_WOLVES {
+                        let (x, y) = (
+                            pos.x + rng.roll_dice(1, 5) - 3,
+                            pos.y + rng.roll_dice(1, 5) - 3,
+                        );
+                        if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 {
+                            continue;
+                        }
+                        if map.blocked[map.xy_idx(x, y)] {
+                            continue;
+                        }
+                        spawn_builder.request(x, y, "WOLF".to_string());
+                        particle_builder.request(
+                            x,
+                            y,
+                            rltk::RGB::named(rltk::MEDIUM_PURPLE),
+                            rltk::RGB::named(rltk::BLACK),
+                            rltk::to_cp437('*'),
+                            400.0,
+                        );
+                    }
+                    StatusEffects::inflict(&mut statuses, entity, StatusKind::Hasted, 10);
+                    log.log(LogEntry::Alert {
+                        alert: format!("{} whistles, and WOLVES come running!", name),
+                    });
+                }
+                BossPhase::Conjuring => {
+                    if stats.hp * 3 <= stats.max_hp {
+                        boss.phase = BossPhase::Humbled;
+                        hostiles.remove(entity);
+                        memories.remove(entity);
+                        statuses.remove(entity);
+                        if let Some(dialogue) = dialogues.get_mut(entity) {
+                            dialogue.tree = HUMBLED_TREE.to_string();
+                        }
+                        // and his wolves stand down with him
+                        for (wolf, _pack) in (&entities, &pack_hunters).join() {
+                            hostiles.remove(wolf);
+                            memories.remove(wolf);
+                        }
+                        log.log(LogEntry::Alert {
+                            alert: format!("{} throws up his hands. \"ENOUGH! let's talk!\"", name),
+                        });
+                        continue;
+                    }
+                    if *runstate != RunState::CoreMonsterTurn || rng.roll_dice(1, 3) != 1 {
+                        continue;
+                    }
+                    // lobs a pastry whenever he can see you
+                    let here = Point::new(pos.x, pos.y);
+                    let sees_player = viewsheds.get(entity).map_or(false, |viewshed| {
+                        viewshed.visible_tiles.contains(&*player_pos)
+                    });
+                    if !sees_player
+                        || rltk::DistanceAlg::Pythagoras.distance2d(here, *player_pos)
+                            > PASTRY_RANGE
+                    {
+                        continue;
+                    }
+                    let (pastry, kind, turns) = PASTRIES[rng.roll_dice(1, 3) as usize - 1];
+                    for (i, step) in rltk::line2d(rltk::LineAlg::Bresenham, here, *player_pos)
+                        .iter()
+                        .skip(1)
+                        .enumerate()
+                    {
+                        particle_builder.request(
+                            step.x,
+                            step.y,
+                            rltk::RGB::from_hex(kind.color()).expect("hardcoded"),
+                            rltk::RGB::named(rltk::BLACK),
+                            rltk::to_cp437('%'),
+                            60.0 + 40.0 * i as f32,
+                        );
+                    }
+                    StatusEffects::inflict(&mut statuses, *player_entity, kind, turns);
+                    log.log(LogEntry::Action {
+                        subject: name,
+                        verb: format!("hurls a {} at", pastry),
+                        object: player_name.clone(),
+                        suffix: format!("! {} is {}!", player_name, kind.name()),
+                    });
+                }
+                BossPhase::Humbled => {}
+            }
+        }
+    }
+}
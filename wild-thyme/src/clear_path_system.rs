@@ -0,0 +1,166 @@
+use rltk::RGB;
+use specs::prelude::*;
+
+use crate::{
+    components::{CutsTrees, Equipped, InBackpack, Position, Viewshed, WantsToClearPath},
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    map_builders::is_set_piece,
+    noise_system::{NoiseBuilder, CHOP_NOISE},
+    particle_system::ParticleBuilder,
+    stats::{LevelStats, OverallStats},
+};
+
+pub const CHOP_WORK: i32 = 6;
+
+// the sharpest thing carried or held
+fn best_cutting_power(
+    owner: Entity,
+    entities: &Entities,
+    cuts_trees: &ReadStorage<CutsTrees>,
+    backpack_items: &ReadStorage<InBackpack>,
+    equipped: &ReadStorage<Equipped>,
+) -> Option<i32> {
+    (entities, cuts_trees)
+        .join()
+        .filter(|(e, _)| {
+            backpack_items
+                .get(*e)
+                .map_or(false, |pack| pack.owner == owner)
+                || equipped.get(*e).map_or(false, |eq| eq.owner == owner)
+        })
+        .map(|(_, cuts)| cuts.power)
+        .max()
+}
+
+pub fn player_cutting_power(ecs: &World) -> Option<i32> {
+    best_cutting_power(
+        *ecs.fetch::<Entity>(),
+        &ecs.entities(),
+        &ecs.read_storage::<CutsTrees>(),
+        &ecs.read_storage::<InBackpack>(),
+        &ecs.read_storage::<Equipped>(),
+    )
+}
+
+// only trees inside the forest can be cut, the edge of the map and hand-built levels stay put
+pub fn can_clear(map: &Map, x: i32, y: i32) -> bool {
+    !is_set_piece(map.depth)
+        && x > 0
+        && x < map.width - 1
+        && y > 0
+        && y < map.height - 1
+        && map.tiles[map.xy_idx(x, y)] == TileType::Wall
+}
+
+pub struct ClearPathSystem {}
+
+impl<'a> System<'a> for ClearPathSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, Map>,
+        WriteStorage<'a, WantsToClearPath>,
+        ReadStorage<'a, CutsTrees>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, Position>,
+        WriteExpect<'a, NoiseBuilder>,
+        WriteStorage<'a, Viewshed>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+        ReadExpect<'a, OverallStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut map,
+            mut wants_clear,
+            cuts_trees,
+            backpack_items,
+            equipped,
+            positions,
+            mut noise_builder,
+            mut viewsheds,
+            mut particle_builder,
+            mut log,
+            mut level_stats,
+            stats,
+        ) = data;
+
+        for (entity, wants) in (&entities, &wants_clear).join() {
+            let is_player = entity == *player_entity;
+            let power = match best_cutting_power(
+                entity,
+                &entities,
+                &cuts_trees,
+                &backpack_items,
+                &equipped,
+            ) {
+                Some(power) => power,
+                None => {
+                    if is_player {
+                        log.log(LogEntry::Notification {
+                            notification: format!(
+                                "{} has nothing sharp enough to cut with.",
+                                stats.name
+                            ),
+                        });
+                    }
+                    continue;
+                }
+            };
+            if !can_clear(&map, wants.x, wants.y) {
+                continue;
+            }
+            let idx = map.xy_idx(wants.x, wants.y);
+            let progress = {
+                let progress = map.chop_progress.entry(idx).or_insert(0);
+                *progress += power;
+                *progress
+            };
+            particle_builder.request(
+                wants.x,
+                wants.y,
+                RGB::from_hex("#c0a070").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437('/'),
+                200.0,
+            );
+
+            // all that hacking carries through the trees
+            if let Some(pos) = positions.get(entity) {
+                noise_builder.request(pos.x, pos.y, CHOP_NOISE);
+            }
+
+            if progress < CHOP_WORK {
+                if is_player {
+                    log.log(LogEntry::Notification {
+                        notification: format!(
+                            "{} hacks at the tree.. ({}/{})",
+                            stats.name, progress, CHOP_WORK
+                        ),
+                    });
+                }
+                continue;
+            }
+            map.chop_progress.remove(&idx);
+            map.tiles[idx] = TileType::Stump;
+            map.blocked[idx] = false;
+            for viewshed in (&mut viewsheds).join() {
+                viewshed.dirty = true;
+            }
+            if is_player {
+                level_stats.trees_cleared += 1;
+                log.log(LogEntry::Alert {
+                    alert: "the tree comes crashing down! CRACK!".to_string(),
+                });
+            }
+        }
+
+        wants_clear.clear();
+    }
+}
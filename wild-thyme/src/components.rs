@@ -47,9 +47,112 @@ pub struct Herbivore {}
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct HostileToPlayer {}
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Predator {}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Scavenger {}
+
+// fear builds up from threats and wounds, and wears off over time
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Morale {
+    pub fear: i32,
+    pub courage: i32,
+    pub last_hp: i32,
+}
+
+impl Morale {
+    pub fn new(courage: i32, hp: i32) -> Morale {
+        Morale {
+            fear: 0,
+            courage,
+            last_hp: hp,
+        }
+    }
+}
+
+// what the ecology wants a mob to do this round, worked out fresh every round
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum Goal {
+    Flee { x: i32, y: i32 },
+    Hunt { x: i32, y: i32 },
+    Scavenge { x: i32, y: i32 },
+    Herd { x: i32, y: i32 },
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Behaviour {
+    pub goal: Goal,
+}
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum AiState {
+    Idle,
+    Wandering,
+    Alerted,
+    Chasing,
+    Searching,
+    Returning,
+    Fleeing,
+}
+
+// what a hostile mob is up to, and what it remembers about the player
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct AiMemory {
+    pub state: AiState,
+    pub home: (i32, i32),
+    pub last_seen: Option<(i32, i32)>,
+    pub forget_timer: i32,
+    pub chasing_player: bool,
+}
+
+impl AiMemory {
+    pub fn new(x: i32, y: i32) -> AiMemory {
+        AiMemory {
+            state: AiState::Idle,
+            home: (x, y),
+            last_seen: None,
+            forget_timer: 0,
+            chasing_player: false,
+        }
+    }
+}
+
+// hunts in packs, spreading out around its quarry
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct PackHunter {}
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum BossPhase {
+    Welcoming,
+    Fighting,
+    Conjuring,
+    Humbled,
+}
+
+// the wizard, who changes tactics as the fight goes on
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct WizardBoss {
+    pub phase: BossPhase,
+}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Creature {}
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Nocturnal {}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct LightSource {
+    pub radius: i32,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct HeardNoise {
+    pub x: i32,
+    pub y: i32,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct Quips {
     pub quips: Vec<String>,
@@ -57,6 +160,17 @@ pub struct Quips {
     pub countdown: i32,
 }
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Dialogue {
+    pub tree: String,
+    pub flags: Vec<String>,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Persona {
+    pub persona: String,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct DropsLoot {
     pub item: Entity,
@@ -76,6 +190,7 @@ pub struct CombatStats {
     pub hp: i32,
     pub defense: i32,
     pub power: i32,
+    pub dodge: i32,
 }
 
 #[derive(Component, ConvertSaveload, Clone)]
@@ -88,6 +203,28 @@ pub struct WantsToSwap {
     pub target: Entity,
 }
 
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum RangedEffect {
+    Damage,
+    KnockItemLoose,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct RangedAttack {
+    pub range: i32,
+    pub damage: i32,
+    pub cooldown: i32,
+    pub cooldown_remaining: i32,
+    pub effect: RangedEffect,
+    pub glyph: rltk::FontCharType,
+    pub color: RGB,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct WantsToShoot {
+    pub target: Entity,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct SufferDamage {
     pub amount: Vec<i32>,
@@ -112,6 +249,24 @@ pub struct Item {}
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Rare {}
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Worth {
+    pub value: i32,
+}
+
+#[derive(PartialEq, Clone, Serialize, Deserialize)]
+pub struct VendorItem {
+    pub name: String,
+    pub price: i32,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Vendor {
+    pub stock_table: String,
+    pub stock: Vec<VendorItem>,
+    pub restocked_depth: i32,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct CakeIngredient {
     pub adjective: String,
@@ -128,6 +283,57 @@ pub struct CakeIngredient {
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct GoodThyme {}
 
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum SpoilState {
+    Fresh,
+    Stale,
+    Rotten,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Perishable {
+    pub base_name: String,
+    pub freshness: i32,
+    pub max_freshness: i32,
+    pub state: SpoilState,
+}
+
+impl Perishable {
+    pub fn new(base_name: &str, freshness: i32) -> Perishable {
+        Perishable {
+            base_name: base_name.to_string(),
+            freshness,
+            max_freshness: freshness,
+            state: SpoilState::Fresh,
+        }
+    }
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct SlowsSpoilage {
+    pub factor: i32,
+}
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum ForagingTool {
+    Basket,
+    Digging,
+    Smoker,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct ForagingGear {
+    pub tool: ForagingTool,
+    pub quality: i32,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Harvestable {
+    pub tool: ForagingTool,
+    pub yields: String,
+    pub harvests: i32,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct InBackpack {
     pub owner: Entity,
@@ -189,24 +395,122 @@ pub struct AreaOfEffect {
     pub radius: i32,
 }
 
-#[derive(Component, ConvertSaveload, Clone)]
-pub struct Confusion {
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum StatusKind {
+    Confused,
+    Poisoned,
+    Slowed,
+    Hasted,
+    Sleepy,
+    Glowing,
+    High,
+}
+
+impl StatusKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusKind::Confused => "CONFUSED",
+            StatusKind::Poisoned => "POISONED",
+            StatusKind::Slowed => "SLOWED",
+            StatusKind::Hasted => "HASTED",
+            StatusKind::Sleepy => "SLEEPY",
+            StatusKind::Glowing => "GLOWING",
+            StatusKind::High => "HIGH",
+        }
+    }
+
+    pub fn glyph(&self) -> char {
+        match self {
+            StatusKind::Confused => '?',
+            StatusKind::Poisoned => '•',
+            StatusKind::Slowed => '_',
+            StatusKind::Hasted => '»',
+            StatusKind::Sleepy => 'z',
+            StatusKind::Glowing => '☼',
+            StatusKind::High => '*',
+        }
+    }
+
+    pub fn color(&self) -> &'static str {
+        match self {
+            StatusKind::Confused => "#a070c0",
+            StatusKind::Poisoned => "#80c040",
+            StatusKind::Slowed => "#8080a0",
+            StatusKind::Hasted => "#e0c080",
+            StatusKind::Sleepy => "#6070c0",
+            StatusKind::Glowing => "#f0f080",
+            StatusKind::High => "#e070a0",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
     pub turns: i32,
 }
 
-impl Confusion {
-    pub fn new_confusion(store: &mut WriteStorage<Confusion>, victim: Entity, amount: i32) {
-        if let Some(suffering) = store.get_mut(victim) {
-            suffering.turns += amount;
-        } else {
-            let confused = Confusion { turns: amount };
-            store
-                .insert(victim, confused)
-                .expect("should be able to insert confused status");
+// everything currently ailing (or helping) an entity, ticked down each of its turns
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    // confusion and poison pile up, everything else just refreshes, and hasted and slowed cancel out
+    pub fn inflict(
+        store: &mut WriteStorage<StatusEffects>,
+        victim: Entity,
+        kind: StatusKind,
+        turns: i32,
+    ) {
+        if !store.contains(victim) {
+            let _ = store.insert(
+                victim,
+                StatusEffects {
+                    effects: Vec::new(),
+                },
+            );
+        }
+        let statuses = store
+            .get_mut(victim)
+            .expect("should be able to insert status effects");
+        let opposite = match kind {
+            StatusKind::Slowed => Some(StatusKind::Hasted),
+            StatusKind::Hasted => Some(StatusKind::Slowed),
+            _ => None,
+        };
+        if let Some(opposite) = opposite {
+            if statuses.has(opposite) {
+                statuses.effects.retain(|effect| effect.kind != opposite);
+                return;
+            }
+        }
+        match statuses
+            .effects
+            .iter_mut()
+            .find(|effect| effect.kind == kind)
+        {
+            Some(effect) => match kind {
+                StatusKind::Confused | StatusKind::Poisoned => effect.turns += turns,
+                _ => effect.turns = i32::max(effect.turns, turns),
+            },
+            None => statuses.effects.push(StatusEffect { kind, turns }),
         }
     }
 }
 
+// items, traps and bites that pass a status on to whatever they get
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct InflictsStatus {
+    pub kind: StatusKind,
+    pub turns: i32,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct SpawnsMobs {
     pub mob_type: String,
@@ -218,6 +522,8 @@ pub struct SerializeMe;
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct SerializationHelper {
     pub map: super::map::Map,
+    pub run_seed: u64,
+    pub clock: crate::day_night_system::WorldClock,
 }
 
 #[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
@@ -237,9 +543,25 @@ pub struct Equipped {
     pub slot: EquipmentSlot,
 }
 
-#[derive(Component, ConvertSaveload, Clone)]
-pub struct MeleePowerBonus {
-    pub power: i32,
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeaponTrait {
+    // hits things two tiles away
+    Reach,
+    // sometimes knocks the target off its next turn
+    Stagger,
+    // sends the target back a tile
+    Knockback,
+}
+
+// damage rolled on top of the wielder's own power
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct MeleeWeapon {
+    pub n_dice: i32,
+    pub die_type: i32,
+    pub bonus: i32,
+    pub hit_bonus: i32,
+    pub traits: Vec<WeaponTrait>,
 }
 
 #[derive(Component, ConvertSaveload, Clone)]
@@ -247,6 +569,96 @@ pub struct DefenseBonus {
     pub defense: i32,
 }
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Warm {}
+
+// clunky gear carries further when walking
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct NoiseModifier {
+    pub amount: i32,
+}
+
+// how quickly something acts compared to the player, energy builds up each round and acting spends it
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Initiative {
+    pub speed: i32,
+    pub energy: i32,
+}
+
+impl Initiative {
+    pub fn new(speed: i32) -> Initiative {
+        Initiative { speed, energy: 0 }
+    }
+}
+
+// sneaking only gets a step in every other turn
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Sneaking {
+    pub half_step: bool,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct CutsTrees {
+    pub power: i32,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct WantsToClearPath {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct WantsToShove {
+    pub x: i32,
+    pub y: i32,
+}
+
+// being sent flying this many tiles in a direction
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Knockback {
+    pub dx: i32,
+    pub dy: i32,
+    pub force: i32,
+}
+
+// hits hard enough to knock the target back
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct KnocksBack {
+    pub force: i32,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct WantsToThrow {
+    pub item: Entity,
+    pub x: i32,
+    pub y: i32,
+}
+
+// how far something can be thrown, and how much it hurts when it lands on someone
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Weight {
+    pub weight: i32,
+}
+
+// breaks instead of landing
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Fragile {
+    pub verb: String,
+}
+
+// thrown food that herbivores will come over for
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Bait {}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Ignites {}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct KnownRecipes {
+    pub recipes: Vec<String>,
+}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct ParticleLifetime {
     pub lifetime_ms: f32,
@@ -266,9 +678,33 @@ pub struct HungerClock {
     pub duration: i32,
 }
 
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum ThirstState {
+    Quenched,
+    Normal,
+    Thirsty,
+    Parched,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct ThirstClock {
+    pub state: ThirstState,
+    pub duration: i32,
+}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct ProvidesFood {}
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct HoldsWater {
+    pub sips: i32,
+    pub capacity: i32,
+    pub contaminated: bool,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct PurifiesWater {}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct MagicMapper {}
 
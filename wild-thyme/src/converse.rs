@@ -0,0 +1,105 @@
+use rltk::VirtualKeyCode;
+use specs::prelude::*;
+
+use crate::{
+    components::{Name, Persona, Quips},
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    menu::key_to_name_char,
+    name_mangler::{fill_name, NAME_PLACEHOLDER},
+    stats::OverallStats,
+    window_fx,
+};
+
+pub const MAX_PLAYER_LINE_LENGTH: usize = 40;
+pub const MAX_REPLY_LENGTH: usize = 120;
+// roughly 10 seconds at 60fps before the npc gives up thinking
+pub const REPLY_TIMEOUT_FRAMES: i32 = 600;
+const RECENT_EVENTS: usize = 8;
+
+pub struct ConversationInput {
+    pub line: String,
+}
+
+impl ConversationInput {
+    pub fn new() -> ConversationInput {
+        ConversationInput {
+            line: String::new(),
+        }
+    }
+}
+
+pub fn key_to_line_char(key: VirtualKeyCode, shift: bool) -> Option<char> {
+    match key {
+        VirtualKeyCode::Comma => Some(','),
+        VirtualKeyCode::Period => Some('.'),
+        VirtualKeyCode::Apostrophe => Some('\''),
+        VirtualKeyCode::Slash if shift => Some('?'),
+        VirtualKeyCode::Key1 if shift => Some('!'),
+        _ => key_to_name_char(key).map(|c| c.to_ascii_lowercase()),
+    }
+}
+
+// asks good-thyme to answer in character, the reply is polled with window_fx::npc_reply
+pub fn send_line(ecs: &World, npc: Entity) {
+    let names = ecs.read_storage::<Name>();
+    let personas = ecs.read_storage::<Persona>();
+    let npc_name = match names.get(npc) {
+        Some(name) => name.name.clone(),
+        None => return,
+    };
+    let persona = match personas.get(npc) {
+        Some(persona) => persona.persona.clone(),
+        None => return,
+    };
+    let recent_events: Vec<String> = ecs.fetch::<GameLog>().recent(RECENT_EVENTS);
+    window_fx::converse(
+        npc_name.clone(),
+        persona,
+        ecs.fetch::<Map>().depth,
+        recent_events,
+        ecs.fetch::<ConversationInput>().line.clone(),
+        fill_name(NAME_PLACEHOLDER, &ecs.fetch::<OverallStats>(), &npc_name),
+    );
+}
+
+// logs both sides of the exchange, falling back to a quip when good-thyme can't answer
+pub fn finish(ecs: &mut World, npc: Entity, reply: Option<String>) {
+    let npc_name = ecs
+        .read_storage::<Name>()
+        .get(npc)
+        .map(|name| name.name.clone())
+        .unwrap_or_default();
+    let reply = match reply {
+        Some(reply) if !reply.trim().is_empty() => {
+            reply.trim().chars().take(MAX_REPLY_LENGTH).collect()
+        }
+        _ => fallback_quip(ecs, npc, &npc_name),
+    };
+    let line = ecs.fetch::<ConversationInput>().line.clone();
+    let player_name = ecs
+        .read_storage::<Name>()
+        .get(*ecs.fetch::<Entity>())
+        .map_or("YOU".to_string(), |name| name.name.clone());
+    let mut log = ecs.fetch_mut::<GameLog>();
+    log.log(LogEntry::Quip {
+        subject: player_name,
+        quip: line,
+    });
+    log.log(LogEntry::Quip {
+        subject: npc_name,
+        quip: reply,
+    });
+}
+
+fn fallback_quip(ecs: &mut World, npc: Entity, npc_name: &str) -> String {
+    let quips = ecs.read_storage::<Quips>();
+    let mut rng = ecs.fetch_mut::<rltk::RandomNumberGenerator>();
+    match quips
+        .get(npc)
+        .and_then(|quips| rng.random_slice_entry(&quips.quips))
+    {
+        Some(quip) => fill_name(quip, &ecs.fetch::<OverallStats>(), npc_name),
+        None => "...".to_string(),
+    }
+}
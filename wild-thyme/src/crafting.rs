@@ -0,0 +1,398 @@
+use rltk::RGB;
+use serde::Deserialize;
+use specs::prelude::*;
+use specs::saveload::{MarkedBuilder, SimpleMarker};
+
+use crate::{
+    components::{
+        Backpack, CakeIngredient, Consumable, EquipmentSlot, Equippable, Ignites, InBackpack, Item,
+        KnownRecipes, LightSource, MeleeWeapon, Name, Perishable, ProvidesFood, ProvidesHealing,
+        Ranged, Renderable, SerializeMe, WeaponTrait, Worth,
+    },
+    gamelog::{GameLog, LogEntry},
+    stats::{LevelStats, OverallStats},
+};
+
+const RECIPE_DATA: &str = include_str!("../resources/recipes.json");
+
+#[derive(Deserialize)]
+pub struct Recipe {
+    pub inputs: Vec<String>,
+    pub output: RecipeOutput,
+}
+
+#[derive(Deserialize)]
+pub struct RecipeOutput {
+    pub name: String,
+    pub glyph: char,
+    pub fg: String,
+    pub adjective: String,
+    pub super_adjective: String,
+    pub kind: OutputKind,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputKind {
+    Food {
+        heal: i32,
+    },
+    Weapon {
+        n_dice: i32,
+        die_type: i32,
+        #[serde(default)]
+        bonus: i32,
+        #[serde(default)]
+        hit_bonus: i32,
+        #[serde(default)]
+        traits: Vec<WeaponTrait>,
+    },
+    Light {
+        radius: i32,
+    },
+}
+
+pub struct RecipeBook {
+    recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    pub fn new() -> RecipeBook {
+        RecipeBook {
+            recipes: serde_json::from_str(RECIPE_DATA)
+                .expect("recipes.json should be valid recipes"),
+        }
+    }
+
+    pub fn recipe(&self, name: &str) -> Option<&Recipe> {
+        self.recipes
+            .iter()
+            .find(|recipe| recipe.output.name == name)
+    }
+
+    // order doesn't matter, but every input has to be there exactly
+    fn matching(&self, names: &[String]) -> Option<&Recipe> {
+        let mut names = names.to_vec();
+        names.sort();
+        self.recipes.iter().find(|recipe| {
+            let mut inputs = recipe.inputs.clone();
+            inputs.sort();
+            inputs == names
+        })
+    }
+}
+
+// the items put together on the crafting screen
+pub struct CraftingSelection {
+    pub items: Vec<Entity>,
+}
+
+impl CraftingSelection {
+    pub fn new() -> CraftingSelection {
+        CraftingSelection { items: Vec::new() }
+    }
+}
+
+// stale milk is still milk as far as recipes are concerned
+pub fn ingredient_name(ecs: &World, item: Entity) -> String {
+    if let Some(perishable) = ecs.read_storage::<Perishable>().get(item) {
+        return perishable.base_name.clone();
+    }
+    ecs.read_storage::<Name>()
+        .get(item)
+        .map(|name| name.name.clone())
+        .unwrap_or_default()
+}
+
+pub fn player_items(ecs: &World) -> Vec<(Entity, String)> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let entities = ecs.entities();
+    let backpack_items = ecs.read_storage::<InBackpack>();
+    let names = ecs.read_storage::<Name>();
+    (&entities, &backpack_items, &names)
+        .join()
+        .filter(|(_, pack, _)| pack.owner == player_entity)
+        .map(|(entity, _, name)| (entity, name.name.clone()))
+        .collect()
+}
+
+pub fn known_recipes(ecs: &World) -> Vec<String> {
+    let player_entity = *ecs.fetch::<Entity>();
+    ecs.read_storage::<KnownRecipes>()
+        .get(player_entity)
+        .map(|known| known.recipes.clone())
+        .unwrap_or_default()
+}
+
+pub fn toggle_selection(ecs: &mut World, item: Entity) {
+    let mut selection = ecs.fetch_mut::<CraftingSelection>();
+    match selection
+        .items
+        .iter()
+        .position(|selected| *selected == item)
+    {
+        Some(i) => {
+            selection.items.remove(i);
+        }
+        None => selection.items.push(item),
+    }
+}
+
+// picks out the inputs for a recipe YOU already know, returns false if something is missing
+pub fn select_recipe(ecs: &mut World, name: &str) -> bool {
+    let inputs = match ecs.fetch::<RecipeBook>().recipe(name) {
+        Some(recipe) => recipe.inputs.clone(),
+        None => return false,
+    };
+    let mut chosen: Vec<Entity> = Vec::new();
+    for input in inputs.iter() {
+        let found = player_items(ecs)
+            .into_iter()
+            .find(|(item, _)| !chosen.contains(item) && ingredient_name(ecs, *item) == *input);
+        match found {
+            Some((item, _)) => chosen.push(item),
+            None => return false,
+        }
+    }
+    ecs.fetch_mut::<CraftingSelection>().items = chosen;
+    true
+}
+
+fn combined_ingredient(ecs: &World, items: &[Entity], output: &RecipeOutput) -> CakeIngredient {
+    let ingredients = ecs.read_storage::<CakeIngredient>();
+    let mut combined = CakeIngredient {
+        adjective: output.adjective.clone(),
+        super_adjective: output.super_adjective.clone(),
+        overall_points: 0,
+        moist_points: 0,
+        sweet_points: 0,
+        style_points: 0,
+        hot_points: 0,
+        mold_points: 0,
+        edible_points: 0,
+    };
+    for ingredient in items.iter().filter_map(|item| ingredients.get(*item)) {
+        combined.overall_points += ingredient.overall_points;
+        combined.moist_points += ingredient.moist_points;
+        combined.sweet_points += ingredient.sweet_points;
+        combined.style_points += ingredient.style_points;
+        combined.hot_points += ingredient.hot_points;
+        combined.mold_points += ingredient.mold_points;
+        combined.edible_points += ingredient.edible_points;
+    }
+    combined
+}
+
+// puts the selected items together, returns whether anything came of it
+pub fn craft(ecs: &mut World) -> bool {
+    let selected: Vec<Entity> = ecs
+        .fetch_mut::<CraftingSelection>()
+        .items
+        .drain(..)
+        .collect();
+    if selected.is_empty() {
+        return false;
+    }
+    let names: Vec<String> = selected
+        .iter()
+        .map(|item| ingredient_name(ecs, *item))
+        .collect();
+
+    let crafted = {
+        let book = ecs.fetch::<RecipeBook>();
+        match book.matching(&names) {
+            Some(recipe) => Some((
+                combined_ingredient(ecs, &selected, &recipe.output),
+                recipe.output.name.clone(),
+                recipe.output.glyph,
+                recipe.output.fg.clone(),
+                recipe.output.kind.clone(),
+            )),
+            None => None,
+        }
+    };
+    let player_name = ecs.fetch::<OverallStats>().name.clone();
+    let (ingredient, output_name, glyph, fg, kind) = match crafted {
+        Some(crafted) => crafted,
+        None => {
+            ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                notification: format!(
+                    "{} fiddles with the {}, but nothing comes of it.",
+                    player_name,
+                    names.join(" and ")
+                ),
+            });
+            return false;
+        }
+    };
+
+    let player_entity = *ecs.fetch::<Entity>();
+    let worth: i32 = {
+        let worths = ecs.read_storage::<Worth>();
+        selected
+            .iter()
+            .map(|item| worths.get(*item).map(|worth| worth.value).unwrap_or(0))
+            .sum()
+    };
+    for item in selected.iter() {
+        ecs.delete_entity(*item)
+            .expect("should be able to delete crafting input");
+    }
+    if let Some(backpack) = ecs.write_storage::<Backpack>().get_mut(player_entity) {
+        backpack.items -= selected.len() as i32 - 1;
+    }
+
+    let output = ecs
+        .create_entity()
+        .with(Renderable {
+            glyph: rltk::to_cp437(glyph),
+            fg: RGB::from_hex(&fg).unwrap_or(RGB::named(rltk::WHITE)),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: output_name.clone(),
+        })
+        .with(Item {})
+        .with(Worth { value: worth + 1 })
+        .with(ingredient)
+        .with(InBackpack {
+            owner: player_entity,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+    match kind {
+        OutputKind::Food { heal } => {
+            let _ = ecs
+                .write_storage::<ProvidesFood>()
+                .insert(output, ProvidesFood {});
+            let _ = ecs
+                .write_storage::<Consumable>()
+                .insert(output, Consumable {});
+            let _ = ecs
+                .write_storage::<ProvidesHealing>()
+                .insert(output, ProvidesHealing { heal_amount: heal });
+        }
+        OutputKind::Weapon {
+            n_dice,
+            die_type,
+            bonus,
+            hit_bonus,
+            traits,
+        } => {
+            let _ = ecs.write_storage::<Equippable>().insert(
+                output,
+                Equippable {
+                    slot: EquipmentSlot::Melee,
+                },
+            );
+            let _ = ecs.write_storage::<MeleeWeapon>().insert(
+                output,
+                MeleeWeapon {
+                    n_dice,
+                    die_type,
+                    bonus,
+                    hit_bonus,
+                    traits,
+                },
+            );
+        }
+        OutputKind::Light { radius } => {
+            let _ = ecs
+                .write_storage::<LightSource>()
+                .insert(output, LightSource { radius });
+            let _ = ecs
+                .write_storage::<Ranged>()
+                .insert(output, Ranged { range: 2 });
+            let _ = ecs.write_storage::<Ignites>().insert(output, Ignites {});
+        }
+    }
+
+    let discovered = {
+        let mut known = ecs.write_storage::<KnownRecipes>();
+        if !known.contains(player_entity) {
+            let _ = known.insert(
+                player_entity,
+                KnownRecipes {
+                    recipes: Vec::new(),
+                },
+            );
+        }
+        let known = known.get_mut(player_entity).expect("just inserted");
+        if known.recipes.contains(&output_name) {
+            false
+        } else {
+            known.recipes.push(output_name.clone());
+            true
+        }
+    };
+
+    let mut stats = ecs.fetch_mut::<LevelStats>();
+    stats.items_crafted += 1;
+    let mut log = ecs.fetch_mut::<GameLog>();
+    if discovered {
+        stats.recipes_discovered += 1;
+        log.log(LogEntry::Alert {
+            alert: format!("{} discovered how to make {}!", player_name, output_name),
+        });
+    }
+    log.log(LogEntry::Action {
+        subject: player_name,
+        verb: format!("combines the {} into", names.join(" and ")),
+        object: output_name,
+        suffix: format!("."),
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    fn crafted(book: &RecipeBook, items: &[&str]) -> Option<String> {
+        book.matching(&names(items))
+            .map(|recipe| recipe.output.name.clone())
+    }
+
+    #[test]
+    fn order_doesnt_matter() {
+        let book = RecipeBook::new();
+        assert_eq!(
+            crafted(&book, &["POINTY STICK", "ROCK"]),
+            Some("SPEAR".to_string())
+        );
+        assert_eq!(
+            crafted(&book, &["ROCK", "POINTY STICK"]),
+            Some("SPEAR".to_string())
+        );
+    }
+
+    #[test]
+    fn every_input_has_to_be_there_exactly() {
+        let book = RecipeBook::new();
+        assert_eq!(
+            crafted(&book, &["ROCK", "POINTY STICK", "ROCK"]),
+            Some("ROCK CLUB".to_string())
+        );
+        assert_eq!(crafted(&book, &["POINTY STICK"]), None);
+        assert_eq!(crafted(&book, &["POINTY STICK", "ROCK", "EGG"]), None);
+        assert_eq!(crafted(&book, &["GOODBERRY", "HONEYCOMB"]), None);
+        assert_eq!(crafted(&book, &[]), None);
+    }
+
+    #[test]
+    fn no_two_recipes_share_inputs() {
+        let book = RecipeBook::new();
+        for recipe in book.recipes.iter() {
+            assert_eq!(
+                book.matching(&recipe.inputs)
+                    .map(|found| found.output.name.as_str()),
+                Some(recipe.output.name.as_str())
+            );
+        }
+    }
+}
@@ -107,7 +107,7 @@ pub fn delete_the_dead(ecs: &mut World) {
                             notification: "".to_string(),
                         });
                         log.log(LogEntry::Alert {
-                            alert: "RIP :(".to_string(),
+                            alert: format!("RIP {} :(", ecs.fetch::<OverallStats>().name),
                         });
                         window_fx::player_died_effect(
                             &ecs.fetch::<OverallStats>(),
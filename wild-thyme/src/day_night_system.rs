@@ -0,0 +1,180 @@
+use rltk::{Point, RandomNumberGenerator, RGB};
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+use crate::{
+    components::{HostileToPlayer, Nocturnal, Viewshed},
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    map_builders::is_set_piece,
+    spawn_system::SpawnBuilder,
+    RunState,
+};
+
+pub const DAY_LENGTH: i32 = 400;
+const DAWN_ENDS: i32 = 50;
+const DAY_ENDS: i32 = 250;
+const DUSK_ENDS: i32 = 300;
+// the adventure starts on a fresh morning
+const STARTING_TURN: i32 = 60;
+const NIGHT_SPAWNS: [&str; 4] = ["NIGHT GHOST", "FIREFLY", "FIREFLY", "NIGHT SPIDER"];
+
+#[derive(PartialEq, Copy, Clone)]
+pub enum DayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorldClock {
+    pub turn: i32,
+}
+
+impl WorldClock {
+    pub fn new() -> WorldClock {
+        WorldClock {
+            turn: STARTING_TURN,
+        }
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        match self.turn % DAY_LENGTH {
+            t if t < DAWN_ENDS => DayPhase::Dawn,
+            t if t < DAY_ENDS => DayPhase::Day,
+            t if t < DUSK_ENDS => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.phase() == DayPhase::Night
+    }
+
+    // how far the player can see without a light
+    pub fn max_sight(&self) -> i32 {
+        match self.phase() {
+            DayPhase::Day => 12,
+            DayPhase::Dawn | DayPhase::Dusk => 8,
+            DayPhase::Night => 4,
+        }
+    }
+
+    pub fn phase_name(&self) -> &str {
+        match self.phase() {
+            DayPhase::Dawn => "DAWN",
+            DayPhase::Day => "DAY",
+            DayPhase::Dusk => "DUSK",
+            DayPhase::Night => "NIGHT",
+        }
+    }
+
+    pub fn tint(&self, fg: RGB) -> RGB {
+        match self.phase() {
+            DayPhase::Day => fg,
+            DayPhase::Dawn => RGB::from_f32(fg.r * 1.0, fg.g * 0.85, fg.b * 0.85),
+            DayPhase::Dusk => RGB::from_f32(fg.r * 0.9, fg.g * 0.7, fg.b * 0.65),
+            DayPhase::Night => RGB::from_f32(fg.r * 0.45, fg.g * 0.5, fg.b * 0.8),
+        }
+    }
+}
+
+// lit tiles glow warm in the dark
+pub fn torchlight(fg: RGB) -> RGB {
+    RGB::from_f32(
+        f32::min(1.0, fg.r * 1.3 + 0.1),
+        f32::min(1.0, fg.g * 1.1 + 0.05),
+        fg.b * 0.8,
+    )
+}
+
+pub struct DayNightSystem {}
+
+impl<'a> System<'a> for DayNightSystem {
+    type SystemData = (
+        WriteExpect<'a, WorldClock>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Map>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Point>,
+        Entities<'a>,
+        ReadStorage<'a, Nocturnal>,
+        WriteStorage<'a, HostileToPlayer>,
+        WriteStorage<'a, Viewshed>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, SpawnBuilder>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            mut clock,
+            runstate,
+            map,
+            player_entity,
+            player_pos,
+            entities,
+            nocturnal,
+            mut hostile,
+            mut viewsheds,
+            mut log,
+            mut spawn_builder,
+            mut rng,
+        ) = data;
+
+        if *runstate != RunState::CorePlayerTurn {
+            return;
+        }
+
+        let old_phase = clock.phase();
+        clock.turn += 1;
+        let phase = clock.phase();
+
+        // lights flicker about at night, so keep the player's view fresh
+        if phase != DayPhase::Day {
+            if let Some(viewshed) = viewsheds.get_mut(*player_entity) {
+                viewshed.dirty = true;
+            }
+        }
+
+        // nocturnal mobs only bother the player after dark
+        for (entity, _nocturnal) in (&entities, &nocturnal).join() {
+            if phase == DayPhase::Night {
+                let _ = hostile.insert(entity, HostileToPlayer {});
+            } else {
+                hostile.remove(entity);
+            }
+        }
+
+        if phase == old_phase {
+            return;
+        }
+        log.log(LogEntry::Notification {
+            notification: match phase {
+                DayPhase::Dawn => "the sky pales as the sun creeps over the trees..",
+                DayPhase::Day => "the forest is bright with morning.",
+                DayPhase::Dusk => "long shadows stretch between the trees..",
+                DayPhase::Night => "night falls over the forest. things stir in the dark..",
+            }
+            .to_string(),
+        });
+
+        if phase == DayPhase::Night && !is_set_piece(map.depth) {
+            for spawn in NIGHT_SPAWNS.iter() {
+                for _try in 0..20 {
+                    let x = rng.range(1, map.width - 1);
+                    let y = rng.range(1, map.height - 1);
+                    let far_enough = rltk::DistanceAlg::Pythagoras
+                        .distance2d(Point::new(x, y), *player_pos)
+                        > 10.0;
+                    let idx = map.xy_idx(x, y);
+                    if far_enough && map.tiles[idx] == TileType::Floor && !map.blocked[idx] {
+                        spawn_builder.request(x, y, spawn.to_string());
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
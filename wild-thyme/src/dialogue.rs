@@ -0,0 +1,372 @@
+use rltk::Point;
+use serde::Deserialize;
+use specs::prelude::*;
+
+use crate::{
+    components::{Backpack, Dialogue, InBackpack, Name},
+    crafting,
+    gamelog::{GameLog, LogEntry},
+    spawn_system::SpawnBuilder,
+    stats::OverallStats,
+};
+
+const DIALOGUE_DATA: &str = include_str!("../resources/dialogue.json");
+
+#[derive(Deserialize)]
+pub struct DialogueTree {
+    pub name: String,
+    pub nodes: Vec<DialogueNode>,
+}
+
+#[derive(Deserialize)]
+pub struct DialogueNode {
+    pub id: String,
+    pub line: String,
+    pub choices: Vec<DialogueChoice>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct DialogueChoice {
+    pub text: String,
+    #[serde(default)]
+    pub conditions: Vec<DialogueCondition>,
+    #[serde(default)]
+    pub effects: Vec<DialogueEffect>,
+    // no next node ends the conversation
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DialogueCondition {
+    HasItem { item: String },
+    LacksItem { item: String },
+    StatAtLeast { stat: String, value: i32 },
+    StatBelow { stat: String, value: i32 },
+    FlagSet { flag: String },
+    FlagNotSet { flag: String },
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DialogueEffect {
+    GiveItem { item: String },
+    TakeItem { item: String },
+    RevealHint { hint: String },
+    SetFlag { flag: String },
+}
+
+pub struct DialogueLibrary {
+    trees: Vec<DialogueTree>,
+}
+
+impl DialogueLibrary {
+    pub fn new() -> DialogueLibrary {
+        DialogueLibrary {
+            trees: serde_json::from_str(DIALOGUE_DATA)
+                .expect("dialogue.json should be valid dialogue trees"),
+        }
+    }
+
+    pub fn tree(&self, name: &str) -> Option<&DialogueTree> {
+        self.trees.iter().find(|tree| tree.name == name)
+    }
+}
+
+impl DialogueTree {
+    pub fn node_index(&self, id: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node.id == id)
+    }
+}
+
+pub fn available_choices(ecs: &World, npc: Entity, node: &DialogueNode) -> Vec<DialogueChoice> {
+    node.choices
+        .iter()
+        .filter(|choice| {
+            choice
+                .conditions
+                .iter()
+                .all(|condition| meets_condition(ecs, npc, condition))
+        })
+        .cloned()
+        .collect()
+}
+
+// applies the chosen option, and returns the next node if the conversation continues
+pub fn choose(ecs: &mut World, npc: Entity, node: usize, choice: usize) -> Option<usize> {
+    let chosen;
+    let next_node;
+    {
+        let library = ecs.fetch::<DialogueLibrary>();
+        let dialogues = ecs.read_storage::<Dialogue>();
+        let tree = library.tree(&dialogues.get(npc)?.tree)?;
+        chosen = available_choices(ecs, npc, tree.nodes.get(node)?)
+            .get(choice)?
+            .clone();
+        next_node = match &chosen.next {
+            Some(id) => tree.node_index(id),
+            None => None,
+        };
+    }
+    for effect in chosen.effects.iter() {
+        apply_effect(ecs, npc, effect);
+    }
+    next_node
+}
+
+fn meets_condition(ecs: &World, npc: Entity, condition: &DialogueCondition) -> bool {
+    match condition {
+        DialogueCondition::HasItem { item } => player_has_item(ecs, item),
+        DialogueCondition::LacksItem { item } => !player_has_item(ecs, item),
+        DialogueCondition::StatAtLeast { stat, value } => {
+            match ecs.fetch::<OverallStats>().stat(stat) {
+                Some(stat_value) => stat_value >= *value,
+                None => false,
+            }
+        }
+        DialogueCondition::StatBelow { stat, value } => {
+            match ecs.fetch::<OverallStats>().stat(stat) {
+                Some(stat_value) => stat_value < *value,
+                None => false,
+            }
+        }
+        DialogueCondition::FlagSet { flag } => npc_has_flag(ecs, npc, flag),
+        DialogueCondition::FlagNotSet { flag } => !npc_has_flag(ecs, npc, flag),
+    }
+}
+
+fn apply_effect(ecs: &mut World, npc: Entity, effect: &DialogueEffect) {
+    let npc_name = ecs
+        .read_storage::<Name>()
+        .get(npc)
+        .map(|name| name.name.clone())
+        .unwrap_or_default();
+    let player_name = ecs.fetch::<OverallStats>().name.clone();
+    match effect {
+        DialogueEffect::GiveItem { item } => {
+            let player_pos = *ecs.fetch::<Point>();
+            ecs.fetch_mut::<SpawnBuilder>()
+                .request(player_pos.x, player_pos.y, item.clone());
+            ecs.fetch_mut::<GameLog>().log(LogEntry::Action {
+                subject: npc_name,
+                verb: format!("hands"),
+                object: player_name,
+                suffix: format!("a {}! [SPACE] to pick it up", item),
+            });
+        }
+        DialogueEffect::TakeItem { item } => {
+            if let Some(taken) = find_player_item(ecs, item) {
+                let player_entity = *ecs.fetch::<Entity>();
+                if let Some(backpack) = ecs.write_storage::<Backpack>().get_mut(player_entity) {
+                    backpack.items -= 1;
+                }
+                ecs.delete_entity(taken)
+                    .expect("should be able to delete traded item");
+                ecs.fetch_mut::<GameLog>().log(LogEntry::Action {
+                    subject: player_name,
+                    verb: format!("hands over the"),
+                    object: item.clone(),
+                    suffix: format!("to {}.", npc_name),
+                });
+            }
+        }
+        DialogueEffect::RevealHint { hint } => {
+            ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                notification: format!("hint: {}", hint),
+            });
+        }
+        DialogueEffect::SetFlag { flag } => {
+            if let Some(dialogue) = ecs.write_storage::<Dialogue>().get_mut(npc) {
+                if !dialogue.flags.contains(flag) {
+                    dialogue.flags.push(flag.clone());
+                }
+            }
+        }
+    }
+}
+
+fn player_has_item(ecs: &World, item: &str) -> bool {
+    find_player_item(ecs, item).is_some()
+}
+
+fn find_player_item(ecs: &World, item: &str) -> Option<Entity> {
+    let player_entity = ecs.fetch::<Entity>();
+    let entities = ecs.entities();
+    let backpack_items = ecs.read_storage::<InBackpack>();
+    (&entities, &backpack_items)
+        .join()
+        .find(|(entity, pack)| {
+            pack.owner == *player_entity && crafting::ingredient_name(ecs, *entity) == item
+        })
+        .map(|(entity, _)| entity)
+}
+
+fn npc_has_flag(ecs: &World, npc: Entity, flag: &str) -> bool {
+    match ecs.read_storage::<Dialogue>().get(npc) {
+        Some(dialogue) => dialogue.flags.iter().any(|f| f == flag),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Perishable;
+
+    fn world_with_player() -> (World, Entity) {
+        let mut ecs = World::new();
+        ecs.register::<InBackpack>();
+        ecs.register::<Name>();
+        ecs.register::<Perishable>();
+        ecs.register::<Dialogue>();
+        let player = ecs.create_entity().build();
+        ecs.insert(player);
+        ecs.insert(OverallStats::new());
+        (ecs, player)
+    }
+
+    fn npc_with_flags(ecs: &mut World, flags: &[&str]) -> Entity {
+        ecs.create_entity()
+            .with(Dialogue {
+                tree: "THE WIZARD".to_string(),
+                flags: flags.iter().map(|flag| flag.to_string()).collect(),
+            })
+            .build()
+    }
+
+    #[test]
+    fn every_choice_leads_somewhere() {
+        let library = DialogueLibrary::new();
+        for tree in library.trees.iter() {
+            assert!(
+                tree.node_index("start").is_some(),
+                "{} has no start",
+                tree.name
+            );
+            for node in tree.nodes.iter() {
+                for choice in node.choices.iter() {
+                    if let Some(next) = &choice.next {
+                        assert!(
+                            tree.node_index(next).is_some(),
+                            "{} / {} leads to missing node {}",
+                            tree.name,
+                            node.id,
+                            next
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spoiled_items_still_count() {
+        let (mut ecs, player) = world_with_player();
+        let npc = npc_with_flags(&mut ecs, &[]);
+        ecs.create_entity()
+            .with(Name {
+                name: "STALE MAGIC MUSHROOM".to_string(),
+            })
+            .with(Perishable::new("MAGIC MUSHROOM", 800))
+            .with(InBackpack { owner: player })
+            .build();
+
+        let has = |item: &str| DialogueCondition::HasItem {
+            item: item.to_string(),
+        };
+        assert!(meets_condition(&ecs, npc, &has("MAGIC MUSHROOM")));
+        assert!(!meets_condition(&ecs, npc, &has("GOOD THYME")));
+        assert!(!meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::LacksItem {
+                item: "MAGIC MUSHROOM".to_string()
+            }
+        ));
+    }
+
+    #[test]
+    fn someone_elses_items_dont_count() {
+        let (mut ecs, _player) = world_with_player();
+        let npc = npc_with_flags(&mut ecs, &[]);
+        ecs.create_entity()
+            .with(Name {
+                name: "GOOD THYME".to_string(),
+            })
+            .with(InBackpack { owner: npc })
+            .build();
+        assert!(!meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::HasItem {
+                item: "GOOD THYME".to_string()
+            }
+        ));
+    }
+
+    #[test]
+    fn flags_and_stats() {
+        let (mut ecs, _player) = world_with_player();
+        let npc = npc_with_flags(&mut ecs, &["fight"]);
+        ecs.fetch_mut::<OverallStats>().deepest_level = 5;
+
+        let flag = |flag: &str| flag.to_string();
+        assert!(meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::FlagSet {
+                flag: flag("fight")
+            }
+        ));
+        assert!(!meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::FlagNotSet {
+                flag: flag("fight")
+            }
+        ));
+        assert!(meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::FlagNotSet {
+                flag: flag("gifted")
+            }
+        ));
+
+        let stat = |stat: &str| stat.to_string();
+        assert!(meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::StatAtLeast {
+                stat: stat("deepest_level"),
+                value: 5
+            }
+        ));
+        assert!(!meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::StatBelow {
+                stat: stat("deepest_level"),
+                value: 5
+            }
+        ));
+        // made-up stats never pass either way
+        assert!(!meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::StatAtLeast {
+                stat: stat("dragons_slain"),
+                value: 0
+            }
+        ));
+        assert!(!meets_condition(
+            &ecs,
+            npc,
+            &DialogueCondition::StatBelow {
+                stat: stat("dragons_slain"),
+                value: 100
+            }
+        ));
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     components::{HighlightObject, Name, Position, Rare, SeenByPlayer, VisibleToPlayer},
     gamelog::{GameLog, LogEntry},
     particle_system::ParticleBuilder,
+    stats::OverallStats,
     RunState, UIConfig,
 };
 
@@ -26,6 +27,7 @@ impl<'a> System<'a> for DiscoverySystem {
         WriteStorage<'a, Position>,
         WriteStorage<'a, HighlightObject>,
         Entities<'a>,
+        ReadExpect<'a, OverallStats>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -42,6 +44,7 @@ impl<'a> System<'a> for DiscoverySystem {
             mut positions,
             mut highlights,
             entities,
+            stats,
         ) = data;
 
         let mut seen_sights = HashSet::new();
@@ -68,7 +71,7 @@ impl<'a> System<'a> for DiscoverySystem {
             let _ = highlights.insert(highlight_entity, HighlightObject {});
             // log to record the new sighting
             log.log(LogEntry::Notification {
-                notification: format!("YOU saw {} for the first time.", name.name),
+                notification: format!("{} saw {} for the first time.", stats.name, name.name),
             });
         }
         // set runstate to view stack of new things and log
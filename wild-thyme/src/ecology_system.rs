@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use rltk::Point;
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        Bait, Behaviour, CombatStats, Goal, Herbivore, HostileToPlayer, Item, Morale, Name,
+        Perishable, Position, Predator, Scavenger, Viewshed,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    RunState,
+};
+
+const MAX_FEAR: i32 = 20;
+const PLAYER_SCARE_RANGE: f32 = 4.0;
+const PREDATOR_SCARE_RANGE: f32 = 7.0;
+const HERD_RANGE: f32 = 10.0;
+const HERD_SPREAD: f32 = 3.0;
+const STAMPEDE_RANGE: f32 = 6.0;
+
+fn distance(a: Point, b: Point) -> f32 {
+    rltk::DistanceAlg::Pythagoras.distance2d(a, b)
+}
+
+// works out whether each mob is fleeing, hunting, scavenging or sticking with its herd
+pub struct EcologySystem {}
+
+impl<'a> System<'a> for EcologySystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Point>,
+        ReadExpect<'a, Map>,
+        ReadStorage<'a, Viewshed>,
+        ReadStorage<'a, Position>,
+        WriteStorage<'a, CombatStats>,
+        ReadStorage<'a, Herbivore>,
+        ReadStorage<'a, Predator>,
+        ReadStorage<'a, Scavenger>,
+        ReadStorage<'a, HostileToPlayer>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Perishable>,
+        WriteStorage<'a, Morale>,
+        WriteStorage<'a, Behaviour>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, Bait>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            runstate,
+            player_pos,
+            map,
+            viewsheds,
+            positions,
+            mut combat_stats,
+            herbivores,
+            predators,
+            scavengers,
+            hostiles,
+            items,
+            names,
+            perishables,
+            mut morale,
+            mut behaviours,
+            mut log,
+            baits,
+        ) = data;
+
+        if *runstate != RunState::CoreMonsterTurn {
+            return;
+        }
+
+        let predator_positions: Vec<(Entity, Point)> = (&entities, &predators, &positions)
+            .join()
+            .map(|(e, _, pos)| (e, Point::new(pos.x, pos.y)))
+            .collect();
+        let prey_positions: Vec<(Entity, Point)> = (&entities, &herbivores, &positions)
+            .join()
+            .map(|(e, _, pos)| (e, Point::new(pos.x, pos.y)))
+            .collect();
+        let meat_positions: Vec<(Entity, Point)> = (&entities, &items, &perishables, &positions)
+            .join()
+            .filter(|(_, _, perishable, _)| perishable.base_name == "RAW MEAT")
+            .map(|(e, _, _, pos)| (e, Point::new(pos.x, pos.y)))
+            .collect();
+        let bait_positions: Vec<(Entity, Point)> = (&entities, &items, &baits, &positions)
+            .join()
+            .map(|(e, _, _, pos)| (e, Point::new(pos.x, pos.y)))
+            .collect();
+        let mut eaten: Vec<Entity> = Vec::new();
+        // panic spreads through a herd
+        let fleeing: Vec<Point> = (&behaviours, &herbivores, &positions)
+            .join()
+            .filter(|(b, _, _)| matches!(b.goal, Goal::Flee { .. }))
+            .map(|(_, _, pos)| Point::new(pos.x, pos.y))
+            .collect();
+        let mut herds: HashMap<String, Vec<Point>> = HashMap::new();
+        for (_, name, pos) in (&herbivores, &names, &positions).join() {
+            herds
+                .entry(name.name.clone())
+                .or_default()
+                .push(Point::new(pos.x, pos.y));
+        }
+
+        let mut new_goals: Vec<(Entity, Option<Goal>)> = Vec::new();
+        for (entity, morale, viewshed, pos, stats) in (
+            &entities,
+            &mut morale,
+            &viewsheds,
+            &positions,
+            &mut combat_stats,
+        )
+            .join()
+        {
+            let here = Point::new(pos.x, pos.y);
+            let sees = |p: &Point| viewshed.visible_tiles.contains(p);
+            let is_herbivore = herbivores.get(entity).is_some();
+
+            // getting hurt is scary, and so is being nearly dead
+            morale.fear = i32::max(0, morale.fear - 1);
+            if stats.hp < morale.last_hp {
+                morale.fear += 2 + morale.last_hp - stats.hp;
+            }
+            if stats.hp * 4 <= stats.max_hp {
+                morale.fear += 2;
+            }
+            morale.last_hp = stats.hp;
+
+            // the nearest thing worth running from
+            let mut threat: Option<Point> = None;
+            if is_herbivore {
+                if sees(&*player_pos) && distance(here, *player_pos) < PLAYER_SCARE_RANGE {
+                    threat = Some(*player_pos);
+                }
+                for (predator, predator_pos) in predator_positions.iter() {
+                    if *predator != entity
+                        && sees(predator_pos)
+                        && distance(here, *predator_pos) < PREDATOR_SCARE_RANGE
+                        && threat
+                            .map_or(true, |t| distance(here, *predator_pos) < distance(here, t))
+                    {
+                        threat = Some(*predator_pos);
+                    }
+                }
+                if threat.is_some() {
+                    morale.fear += 3;
+                } else if fleeing
+                    .iter()
+                    .any(|p| *p != here && distance(here, *p) < STAMPEDE_RANGE)
+                {
+                    morale.fear += 2;
+                    threat = Some(*player_pos);
+                }
+            } else if hostiles.get(entity).is_some() {
+                threat = Some(*player_pos);
+            }
+            morale.fear = i32::min(MAX_FEAR, morale.fear);
+
+            let mut goal = None;
+            if morale.fear >= morale.courage {
+                if let Some(threat) = threat {
+                    goal = Some(Goal::Flee {
+                        x: threat.x,
+                        y: threat.y,
+                    });
+                }
+            }
+
+            // predators go for prey when it's closer than the player
+            if goal.is_none() && predators.get(entity).is_some() {
+                let player_distance = match sees(&*player_pos) {
+                    true => distance(here, *player_pos),
+                    false => f32::MAX,
+                };
+                let prey = prey_positions
+                    .iter()
+                    .filter(|(prey, prey_pos)| *prey != entity && sees(prey_pos))
+                    .map(|(_, prey_pos)| *prey_pos)
+                    .min_by(|a, b| {
+                        distance(here, *a)
+                            .partial_cmp(&distance(here, *b))
+                            .expect("distances should compare")
+                    });
+                if let Some(prey) = prey {
+                    if distance(here, prey) < player_distance {
+                        goal = Some(Goal::Hunt {
+                            x: prey.x,
+                            y: prey.y,
+                        });
+                    }
+                }
+            }
+
+            // scavengers won't walk past a free meal, unless the player is right there
+            if goal.is_none()
+                && scavengers.get(entity).is_some()
+                && distance(here, *player_pos) > 2.0
+            {
+                let meal = meat_positions
+                    .iter()
+                    .filter(|(meat, p)| !eaten.contains(meat) && (sees(p) || *p == here))
+                    .min_by(|a, b| {
+                        distance(here, a.1)
+                            .partial_cmp(&distance(here, b.1))
+                            .expect("distances should compare")
+                    });
+                match meal {
+                    Some((meat, meal)) if distance(here, *meal) < 1.5 => {
+                        eaten.push(*meat);
+                        stats.hp = i32::min(stats.max_hp, stats.hp + 2);
+                        morale.last_hp = stats.hp;
+                        if map.visible_tiles[map.xy_idx(pos.x, pos.y)] {
+                            if let Some(name) = names.get(entity) {
+                                log.log(LogEntry::Notification {
+                                    notification: format!(
+                                        "the {} gobbles up some RAW MEAT.",
+                                        name.name
+                                    ),
+                                });
+                            }
+                        }
+                    }
+                    Some((_, meal)) => {
+                        goal = Some(Goal::Scavenge {
+                            x: meal.x,
+                            y: meal.y,
+                        });
+                    }
+                    None => {}
+                }
+            }
+
+            // and grazers can't resist a snack thrown their way
+            if goal.is_none() && is_herbivore {
+                let snack = bait_positions
+                    .iter()
+                    .filter(|(bait, p)| !eaten.contains(bait) && (sees(p) || *p == here))
+                    .min_by(|a, b| {
+                        distance(here, a.1)
+                            .partial_cmp(&distance(here, b.1))
+                            .expect("distances should compare")
+                    });
+                match snack {
+                    Some((bait, snack)) if distance(here, *snack) < 1.5 => {
+                        eaten.push(*bait);
+                        stats.hp = i32::min(stats.max_hp, stats.hp + 2);
+                        morale.last_hp = stats.hp;
+                        if map.visible_tiles[map.xy_idx(pos.x, pos.y)] {
+                            if let (Some(name), Some(food)) = (names.get(entity), names.get(*bait))
+                            {
+                                log.log(LogEntry::Notification {
+                                    notification: format!(
+                                        "the {} munches on the {}.",
+                                        name.name, food.name
+                                    ),
+                                });
+                            }
+                        }
+                    }
+                    Some((_, snack)) => {
+                        goal = Some(Goal::Scavenge {
+                            x: snack.x,
+                            y: snack.y,
+                        });
+                    }
+                    None => {}
+                }
+            }
+
+            // herbivores drift back towards the rest of their kind
+            if goal.is_none() && is_herbivore {
+                if let Some(herd) = names.get(entity).and_then(|name| herds.get(&name.name)) {
+                    let nearby: Vec<&Point> = herd
+                        .iter()
+                        .filter(|p| **p != here && distance(here, **p) < HERD_RANGE)
+                        .collect();
+                    if !nearby.is_empty() {
+                        let center = Point::new(
+                            nearby.iter().map(|p| p.x).sum::<i32>() / nearby.len() as i32,
+                            nearby.iter().map(|p| p.y).sum::<i32>() / nearby.len() as i32,
+                        );
+                        if distance(here, center) > HERD_SPREAD {
+                            goal = Some(Goal::Herd {
+                                x: center.x,
+                                y: center.y,
+                            });
+                        }
+                    }
+                }
+            }
+
+            let was_fleeing = behaviours
+                .get(entity)
+                .map_or(false, |b| matches!(b.goal, Goal::Flee { .. }));
+            if !was_fleeing
+                && matches!(goal, Some(Goal::Flee { .. }))
+                && map.visible_tiles[map.xy_idx(pos.x, pos.y)]
+            {
+                if let Some(name) = names.get(entity) {
+                    log.log(LogEntry::Notification {
+                        notification: format!("the {} panics and flees!", name.name),
+                    });
+                }
+            }
+            new_goals.push((entity, goal));
+        }
+
+        for food in eaten {
+            entities
+                .delete(food)
+                .expect("should be able to delete eaten food");
+        }
+        for (entity, goal) in new_goals {
+            match goal {
+                Some(goal) => {
+                    let _ = behaviours.insert(entity, Behaviour { goal });
+                }
+                None => {
+                    behaviours.remove(entity);
+                }
+            }
+        }
+    }
+}
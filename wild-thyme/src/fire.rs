@@ -0,0 +1,330 @@
+use rltk::{Point, RandomNumberGenerator, RGB};
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        AreaOfEffect, CombatStats, Ignites, Name, SufferDamage, Viewshed, WantsToUseItem,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    map_builders::is_set_piece,
+    particle_system::ParticleBuilder,
+    stats::LevelStats,
+    weather::Weather,
+    RunState,
+};
+
+const BURN_ROUNDS: i32 = 6;
+const FIRE_DAMAGE: i32 = 2;
+const MAX_FIRE: usize = 60;
+// the most tiles a single blaze can catch, however dry the forest
+const FIRE_FUEL: i32 = 30;
+
+pub fn is_flammable(tile: TileType) -> bool {
+    matches!(tile, TileType::Floor | TileType::Wall | TileType::Stump)
+}
+
+// trees catch easily, the grass underfoot hardly at all
+pub fn spread_chance(tile: TileType) -> i32 {
+    match tile {
+        TileType::Wall => 4,
+        TileType::Stump => 3,
+        _ => 40,
+    }
+}
+
+fn next_to_water(map: &Map, idx: usize) -> bool {
+    let (x, y) = (idx as i32 % map.width, idx as i32 / map.width);
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < 0 || nx > map.width - 1 || ny < 0 || ny > map.height - 1 {
+                continue;
+            }
+            if map.tiles[map.xy_idx(nx, ny)] == TileType::Water {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+pub fn ignite(map: &mut Map, x: i32, y: i32) -> bool {
+    // the town and the wizard's clearing are too damp to catch
+    if is_set_piece(map.depth) {
+        return false;
+    }
+    if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 {
+        return false;
+    }
+    let idx = map.xy_idx(x, y);
+    if !is_flammable(map.tiles[idx]) || map.fire.contains_key(&idx) || map.fire.len() >= MAX_FIRE {
+        return false;
+    }
+    // a fresh fire starts with a full tank, and dies down once it's spent
+    if map.fire.is_empty() {
+        map.fire_fuel = FIRE_FUEL;
+    }
+    if map.fire_fuel <= 0 {
+        return false;
+    }
+    map.fire_fuel -= 1;
+    map.fire.insert(idx, BURN_ROUNDS);
+    true
+}
+
+// lighting things up with torches and sparkling powder, runs before the UseItemSystem
+pub struct IgniteSystem {}
+
+impl<'a> System<'a> for IgniteSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, Map>,
+        ReadStorage<'a, WantsToUseItem>,
+        ReadStorage<'a, Ignites>,
+        ReadStorage<'a, AreaOfEffect>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut map,
+            wants_use,
+            ignites,
+            aoe,
+            mut rng,
+            mut log,
+            mut level_stats,
+        ) = data;
+
+        for (entity, used_item) in (&entities, &wants_use).join() {
+            if ignites.get(used_item.item).is_none() {
+                continue;
+            }
+            let target = match used_item.target {
+                Some(target) => target,
+                None => continue,
+            };
+            let mut lit = 0;
+            match aoe.get(used_item.item) {
+                None => {
+                    if ignite(&mut map, target.x, target.y) {
+                        lit += 1;
+                    }
+                }
+                Some(area_effect) => {
+                    // sparks only catch here and there
+                    for tile in rltk::field_of_view(target, area_effect.radius, &*map) {
+                        if rng.roll_dice(1, 3) == 1 && ignite(&mut map, tile.x, tile.y) {
+                            lit += 1;
+                        }
+                    }
+                }
+            }
+            if entity != *player_entity {
+                continue;
+            }
+            if lit == 0 {
+                if aoe.get(used_item.item).is_none() {
+                    log.log(LogEntry::Notification {
+                        notification: "nothing there will catch fire.".to_string(),
+                    });
+                }
+            } else {
+                level_stats.fires_started += 1;
+                log.log(LogEntry::Alert {
+                    alert: "flames leap up from the undergrowth!".to_string(),
+                });
+            }
+        }
+    }
+}
+
+pub struct FireSystem {}
+
+impl<'a> System<'a> for FireSystem {
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        WriteExpect<'a, Map>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, Viewshed>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            runstate,
+            mut map,
+            combat_stats,
+            names,
+            mut inflict_damage,
+            mut viewsheds,
+            mut particle_builder,
+            mut rng,
+            mut log,
+            mut level_stats,
+        ) = data;
+
+        if *runstate != RunState::CoreMonsterTurn || map.fire.is_empty() {
+            return;
+        }
+
+        let mut burning: Vec<(usize, i32)> = map.fire.iter().map(|(i, t)| (*i, *t)).collect();
+        burning.sort();
+        let mut spread_to = Vec::new();
+        let mut burnt_out = Vec::new();
+        let mut doused = 0;
+
+        for (idx, rounds) in burning.iter() {
+            let (x, y) = (*idx as i32 % map.width, *idx as i32 / map.width);
+
+            // rain and nearby water put the flames out
+            let rained_out = map.weather == Weather::Rain && rng.roll_dice(1, 3) == 1;
+            if rained_out || (next_to_water(&map, *idx) && rng.roll_dice(1, 2) == 1) {
+                map.fire.remove(idx);
+                doused += 1;
+                particle_builder.request(
+                    x,
+                    y,
+                    RGB::from_hex("#a0a0a0").expect("hardcoded"),
+                    RGB::named(rltk::BLACK),
+                    rltk::to_cp437('░'),
+                    300.0,
+                );
+                continue;
+            }
+
+            for e in map.tile_content[*idx].iter() {
+                if combat_stats.get(*e).is_none() {
+                    continue;
+                }
+                SufferDamage::new_damage(&mut inflict_damage, *e, FIRE_DAMAGE);
+                if *e == *player_entity {
+                    if let Some(name) = names.get(*e) {
+                        log.log(LogEntry::Alert {
+                            alert: format!("{} is burned by the flames!", name.name),
+                        });
+                    }
+                } else if map.visible_tiles[*idx] {
+                    if let Some(name) = names.get(*e) {
+                        log.log(LogEntry::Notification {
+                            notification: format!("the {} is burned by the flames!", name.name),
+                        });
+                    }
+                }
+            }
+
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if nx < 1 || nx > map.width - 2 || ny < 1 || ny > map.height - 2 {
+                        continue;
+                    }
+                    let n_idx = map.xy_idx(nx, ny);
+                    let tile = map.tiles[n_idx];
+                    if is_flammable(tile) && rng.roll_dice(1, spread_chance(tile)) == 1 {
+                        spread_to.push(Point::new(nx, ny));
+                    }
+                }
+            }
+
+            particle_builder.request(
+                x,
+                y,
+                RGB::named(rltk::ORANGE),
+                RGB::from_hex("#401000").expect("hardcoded"),
+                rltk::to_cp437('▲'),
+                200.0 + rng.range(0, 200) as f32,
+            );
+
+            if *rounds <= 1 {
+                burnt_out.push(*idx);
+            } else {
+                map.fire.insert(*idx, rounds - 1);
+            }
+        }
+
+        // burnt trees leave ash behind that can be walked over
+        let mut opened_up = false;
+        for idx in burnt_out.iter() {
+            map.fire.remove(idx);
+            if map.tiles[*idx] == TileType::Wall {
+                opened_up = true;
+            }
+            map.tiles[*idx] = TileType::Ash;
+            map.blocked[*idx] = false;
+            map.chop_progress.remove(idx);
+            level_stats.tiles_burned += 1;
+        }
+        if opened_up {
+            for viewshed in (&mut viewsheds).join() {
+                viewshed.dirty = true;
+            }
+        }
+
+        for p in spread_to.iter() {
+            ignite(&mut map, p.x, p.y);
+        }
+
+        if map.fire.is_empty() {
+            log.log(LogEntry::Notification {
+                notification: match doused {
+                    0 => "the fire burns itself out.".to_string(),
+                    _ => "the last of the flames hiss out.".to_string(),
+                },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grass_catches_far_slower_than_trees() {
+        assert!(is_flammable(TileType::Wall));
+        assert!(!is_flammable(TileType::Water));
+        assert!(spread_chance(TileType::Floor) >= spread_chance(TileType::Wall) * 5);
+        assert!(spread_chance(TileType::Stump) <= spread_chance(TileType::Floor));
+    }
+
+    #[test]
+    fn set_pieces_dont_burn() {
+        for depth in [1, 11] {
+            let mut map = Map::new(depth);
+            assert!(!ignite(&mut map, 10, 10));
+            assert!(map.fire.is_empty());
+        }
+    }
+
+    #[test]
+    fn one_fire_only_catches_so_many_tiles() {
+        let mut map = Map::new(2);
+        let mut lit = 0;
+        for y in 1..map.height - 1 {
+            for x in 1..map.width - 1 {
+                if ignite(&mut map, x, y) {
+                    lit += 1;
+                }
+            }
+        }
+        assert_eq!(lit, FIRE_FUEL);
+
+        // once it's out, a new fire gets a fresh tank
+        map.fire.clear();
+        assert!(ignite(&mut map, 1, 1));
+    }
+}
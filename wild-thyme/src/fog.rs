@@ -0,0 +1,203 @@
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+use crate::{
+    components::{Fog, Position, Viewshed},
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    map_builders::is_set_piece,
+    particle_system::ParticleBuilder,
+    spawn_system::SpawnBuilder,
+    spawners,
+    stats::OverallStats,
+    weather::Weather,
+    RunState,
+};
+
+pub const FOG_BANK_LIFETIME: i32 = 80;
+pub const FOG_SPREAD_LIFETIME: i32 = 25;
+const FOG_BANK_SIZE: i32 = 5;
+const MAX_FOG: usize = 40;
+// fog won't roll in right on top of the player
+const MIN_FOG_DISTANCE: f32 = 12.0;
+
+pub struct FogSystem {}
+
+impl<'a> System<'a> for FogSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadExpect<'a, RunState>,
+        Entities<'a>,
+        WriteStorage<'a, Fog>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, Viewshed>,
+        WriteExpect<'a, SpawnBuilder>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            mut map,
+            runstate,
+            entities,
+            mut fogs,
+            mut positions,
+            mut viewsheds,
+            mut spawn_builder,
+            mut particle_builder,
+            mut rng,
+        ) = data;
+
+        // fog only evolves once a round
+        if *runstate == RunState::CoreMonsterTurn {
+            let fog_count = (&fogs, &positions).join().count();
+            let mut fogged: Vec<usize> = (&fogs, &positions)
+                .join()
+                .map(|(_fog, pos)| map.xy_idx(pos.x, pos.y))
+                .collect();
+
+            for (entity, fog, pos) in (&entities, &mut fogs, &mut positions).join() {
+                fog.lifetime_rounds -= 1;
+                if fog.lifetime_rounds < 1 {
+                    particle_builder.request(
+                        pos.x,
+                        pos.y,
+                        rltk::RGB::from_hex("#8a7fa0").expect("hardcoded"),
+                        rltk::RGB::named(rltk::BLACK),
+                        rltk::to_cp437('░'),
+                        300.0,
+                    );
+                    entities
+                        .delete(entity)
+                        .expect("fog system should be able to delete expired fog");
+                    continue;
+                }
+
+                // drift
+                if rng.roll_dice(1, 3) == 1 {
+                    let (x, y) = (
+                        pos.x + rng.roll_dice(1, 3) - 2,
+                        pos.y + rng.roll_dice(1, 3) - 2,
+                    );
+                    if can_hold_fog(&map, x, y) && !fogged.contains(&map.xy_idx(x, y)) {
+                        let old_idx = map.xy_idx(pos.x, pos.y);
+                        fogged.retain(|idx| *idx != old_idx);
+                        pos.x = x;
+                        pos.y = y;
+                        fogged.push(map.xy_idx(x, y));
+                    }
+                }
+
+                // spread
+                if fog_count < MAX_FOG && rng.roll_dice(1, 12) == 1 {
+                    let (x, y) = (
+                        pos.x + rng.roll_dice(1, 3) - 2,
+                        pos.y + rng.roll_dice(1, 3) - 2,
+                    );
+                    if can_hold_fog(&map, x, y) && !fogged.contains(&map.xy_idx(x, y)) {
+                        spawn_builder.request(x, y, "FOG".to_string());
+                        fogged.push(map.xy_idx(x, y));
+                    }
+                }
+            }
+
+            for viewshed in (&mut viewsheds).join() {
+                viewshed.dirty = true;
+            }
+        }
+
+        map.fog_tiles.clear();
+        for (_fog, pos) in (&fogs, &positions).join() {
+            let idx = map.xy_idx(pos.x, pos.y);
+            map.fog_tiles.insert(idx);
+        }
+    }
+}
+
+fn can_hold_fog(map: &Map, x: i32, y: i32) -> bool {
+    if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 {
+        return false;
+    }
+    map.tiles[map.xy_idx(x, y)] == TileType::Floor
+}
+
+// rolls a bank of fog in somewhere away from the player
+pub fn spawn_fog_bank(ecs: &mut World) {
+    if is_set_piece(ecs.fetch::<Map>().depth) {
+        return;
+    }
+    let mut bank = Vec::new();
+    {
+        let map = ecs.fetch::<Map>();
+        let player_pos = *ecs.fetch::<Point>();
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        let candidates: Vec<Point> = (0..map.tiles.len())
+            .map(|idx| Point::new(idx as i32 % map.width, idx as i32 / map.width))
+            .filter(|p| can_hold_fog(&map, p.x, p.y))
+            .filter(|p| rltk::DistanceAlg::Pythagoras.distance2d(*p, player_pos) > MIN_FOG_DISTANCE)
+            .collect();
+        let center = match rng.random_slice_entry(&candidates) {
+            Some(center) => *center,
+            None => return,
+        };
+        bank.push(center);
+        for _i in 1..FOG_BANK_SIZE {
+            let (x, y) = (
+                center.x + rng.roll_dice(1, 5) - 3,
+                center.y + rng.roll_dice(1, 5) - 3,
+            );
+            if can_hold_fog(&map, x, y) && !bank.contains(&Point::new(x, y)) {
+                bank.push(Point::new(x, y));
+            }
+        }
+    }
+    for p in bank.iter() {
+        let lifetime =
+            FOG_BANK_LIFETIME + ecs.write_resource::<RandomNumberGenerator>().range(0, 20);
+        spawners::fog(ecs, p.x, p.y, lifetime);
+    }
+}
+
+// the fog creeps back in once the last of it has burnt off
+pub fn maybe_roll_in(ecs: &mut World) {
+    // the town is too cozy for fog, and the wizard keeps his clearing clear
+    if is_set_piece(ecs.fetch::<Map>().depth) || ecs.read_storage::<Fog>().join().next().is_some() {
+        return;
+    }
+    // foggy weather keeps it coming
+    let chance = match ecs.fetch::<Map>().weather {
+        Weather::Fog => 8,
+        _ => 30,
+    };
+    if ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(1, chance)
+        == 1
+    {
+        spawn_fog_bank(ecs);
+        ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+            notification: "a strange fog rolls in through the trees..".to_string(),
+        });
+    }
+}
+
+pub fn player_in_fog(ecs: &World) -> bool {
+    let player_pos = *ecs.fetch::<Point>();
+    let fogs = ecs.read_storage::<Fog>();
+    let positions = ecs.read_storage::<Position>();
+    (&fogs, &positions)
+        .join()
+        .any(|(_fog, pos)| pos.x == player_pos.x && pos.y == player_pos.y)
+}
+
+pub fn swallow_player(ecs: &mut World) -> RunState {
+    let player_name = ecs.fetch::<OverallStats>().name.clone();
+    ecs.fetch_mut::<GameLog>().log(LogEntry::Alert {
+        alert: format!("the MYSTERIOUS FOG swallows {} whole..", player_name),
+    });
+    RunState::CoreFadeToNextLevel {
+        level: ecs.fetch::<Map>().depth + 1,
+        row: 0,
+    }
+}
@@ -0,0 +1,154 @@
+use rltk::{Point, RandomNumberGenerator, RGB};
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        Equipped, ForagingGear, ForagingTool, Harvestable, InBackpack, Name, SufferDamage,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    particle_system::ParticleBuilder,
+    spawn_system::SpawnBuilder,
+    stats::{LevelStats, OverallStats},
+};
+
+fn tool_name(tool: ForagingTool) -> &'static str {
+    match tool {
+        ForagingTool::Basket => "a BASKET",
+        ForagingTool::Digging => "something to dig with, like a POINTY STICK",
+        ForagingTool::Smoker => "a SMOKER",
+    }
+}
+
+fn harvestable_nearby(ecs: &World) -> Option<Entity> {
+    let map = ecs.fetch::<Map>();
+    let player_pos = *ecs.fetch::<Point>();
+    let harvestables = ecs.read_storage::<Harvestable>();
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let (x, y) = (player_pos.x + dx, player_pos.y + dy);
+            if x < 0 || x > map.width - 1 || y < 0 || y > map.height - 1 {
+                continue;
+            }
+            for e in map.tile_content[map.xy_idx(x, y)].iter() {
+                if harvestables.get(*e).is_some() {
+                    return Some(*e);
+                }
+            }
+        }
+    }
+    None
+}
+
+// the best matching tool the player is carrying or holding
+fn best_tool(ecs: &World, tool: ForagingTool) -> Option<i32> {
+    let player_entity = *ecs.fetch::<Entity>();
+    let gear = ecs.read_storage::<ForagingGear>();
+    let backpack_items = ecs.read_storage::<InBackpack>();
+    let equipped = ecs.read_storage::<Equipped>();
+    let entities = ecs.entities();
+    (&entities, &gear)
+        .join()
+        .filter(|(e, gear)| {
+            gear.tool == tool
+                && (backpack_items
+                    .get(*e)
+                    .map_or(false, |pack| pack.owner == player_entity)
+                    || equipped
+                        .get(*e)
+                        .map_or(false, |eq| eq.owner == player_entity))
+        })
+        .map(|(_, gear)| gear.quality)
+        .max()
+}
+
+// gathers ingredients from a feature next to the player
+pub fn try_harvest(ecs: &mut World) -> bool {
+    let feature = match harvestable_nearby(ecs) {
+        Some(feature) => feature,
+        None => return false,
+    };
+    let (tool, yields) = {
+        let harvestables = ecs.read_storage::<Harvestable>();
+        let harvestable = harvestables.get(feature).expect("just found it");
+        (harvestable.tool, harvestable.yields.clone())
+    };
+    let feature_name = ecs
+        .read_storage::<Name>()
+        .get(feature)
+        .map(|name| name.name.clone())
+        .unwrap_or_default();
+    let player_entity = *ecs.fetch::<Entity>();
+    let player_pos = *ecs.fetch::<Point>();
+    let player_name = ecs.fetch::<OverallStats>().name.clone();
+
+    let quality = match best_tool(ecs, tool) {
+        Some(quality) => quality,
+        None => {
+            let mut log = ecs.fetch_mut::<GameLog>();
+            log.log(LogEntry::Notification {
+                notification: format!(
+                    "{} needs {} to harvest the {}.",
+                    player_name,
+                    tool_name(tool),
+                    feature_name
+                ),
+            });
+            // bees don't take kindly to bare hands
+            if tool == ForagingTool::Smoker {
+                log.log(LogEntry::Alert {
+                    alert: format!("the angry bees sting {}!", player_name),
+                });
+                SufferDamage::new_damage(
+                    &mut ecs.write_storage::<SufferDamage>(),
+                    player_entity,
+                    1,
+                );
+            }
+            return true;
+        }
+    };
+
+    // better tools gather more
+    let amount = ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(1, quality + 1);
+    {
+        let mut spawn_builder = ecs.fetch_mut::<SpawnBuilder>();
+        for _i in 0..amount {
+            spawn_builder.request(player_pos.x, player_pos.y, yields.clone());
+        }
+    }
+    ecs.fetch_mut::<ParticleBuilder>().request(
+        player_pos.x,
+        player_pos.y,
+        RGB::from_hex("#a0e080").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+        rltk::to_cp437('♦'),
+        200.0,
+    );
+    ecs.fetch_mut::<LevelStats>().ingredients_foraged += amount;
+
+    let picked_clean = {
+        let mut harvestables = ecs.write_storage::<Harvestable>();
+        let harvestable = harvestables.get_mut(feature).expect("just found it");
+        harvestable.harvests -= 1;
+        harvestable.harvests < 1
+    };
+    let mut log = ecs.fetch_mut::<GameLog>();
+    log.log(LogEntry::Notification {
+        notification: format!(
+            "{} harvests {} {} from the {}.",
+            player_name, amount, yields, feature_name
+        ),
+    });
+    if picked_clean {
+        log.log(LogEntry::Notification {
+            notification: format!("the {} is picked clean.", feature_name),
+        });
+        drop(log);
+        ecs.delete_entity(feature)
+            .expect("should be able to delete harvested feature");
+    }
+    true
+}
@@ -1,11 +1,14 @@
 use crate::{
     components::{
-        HighlightObject, Ranged, TeleportsPlayer, Viewshed, WantsToDropItem, WantsToRemoveItem,
-        WantsToUseItem,
+        HighlightObject, Persona, Ranged, TeleportsPlayer, Viewshed, WantsToClearPath,
+        WantsToDropItem, WantsToRemoveItem, WantsToShove, WantsToThrow, WantsToUseItem, Weight,
     },
-    discovery_system,
+    converse::{self, ConversationInput},
+    crafting::{self, CraftingSelection},
+    day_night_system::WorldClock,
+    dialogue, discovery_system, fog,
     gamelog::GameLog,
-    gui,
+    gui, initiative_system,
     map::{Map, MAPHEIGHT, MAPWIDTH},
     menu,
     player::*,
@@ -14,7 +17,9 @@ use crate::{
     spawners,
     stats::{LevelStats, OverallStats},
     stats_system::StatsSystem,
-    window_fx, RunState, State, UIConfig,
+    targeting, throw_system,
+    trade::{self, BarterOffer},
+    weather, window_fx, RunState, State, UIConfig,
 };
 use rltk::Rltk;
 use specs::prelude::*;
@@ -56,6 +61,9 @@ impl State {
             RunState::CorePlayerTurn => {
                 self.ecs.fetch_mut::<GameLog>().mark_all_read();
                 self.run_systems();
+                if fog::player_in_fog(&self.ecs) {
+                    return fog::swallow_player(&mut self.ecs);
+                }
 
                 return match *self.ecs.fetch::<RunState>() {
                     RunState::ActionMagicMapReveal { .. } => RunState::ActionMagicMapReveal {
@@ -65,8 +73,11 @@ impl State {
                     _ => RunState::CoreMonsterTurn,
                 };
             }
-            RunState::CoreMonsterTurn => {
+            RunState::CoreMonsterTurn | RunState::CoreMonsterExtraTurn => {
                 self.run_systems();
+                if initiative_system::anyone_ready(&self.ecs) {
+                    return RunState::CoreMonsterExtraTurn;
+                }
                 return RunState::CorePostRound;
             }
             RunState::CorePostRound => {
@@ -103,13 +114,24 @@ impl State {
                     let mut viewshed_components = self.ecs.write_storage::<Viewshed>();
                     let vs = viewshed_components.get_mut(*player_entity);
                     if let Some(vs) = vs {
-                        if self.ecs.fetch::<LevelStats>().steps_taken > 0 && vs.range < 12 {
+                        // eyes adjust up to what the time of day allows
+                        let max_sight = self.ecs.fetch::<WorldClock>().max_sight();
+                        if vs.range > max_sight {
+                            vs.range = max_sight;
+                            vs.dirty = true;
+                        } else if self.ecs.fetch::<LevelStats>().steps_taken > 0
+                            && vs.range < max_sight
+                        {
                             vs.range += 1;
                             vs.dirty = true;
                         }
                     }
                 }
+                fog::maybe_roll_in(&mut self.ecs);
                 self.ecs.maintain();
+                if fog::player_in_fog(&self.ecs) {
+                    return fog::swallow_player(&mut self.ecs);
+                }
                 return RunState::CorePreRound;
             }
 
@@ -134,7 +156,7 @@ impl State {
                 {
                     let mut stats = self.ecs.fetch_mut::<OverallStats>();
                     let mut level_stats = self.ecs.fetch_mut::<LevelStats>();
-                    stats.apply_level(*level_stats);
+                    stats.apply_level(&level_stats);
                     window_fx::narrate(&stats, &level_stats);
                     level_stats.reset(level);
                 }
@@ -152,16 +174,20 @@ impl State {
                         let item_entity = result.1.expect(
                             "show_inventory always should return entity with Selected response",
                         );
-                        let is_ranged = self.ecs.read_storage::<Ranged>();
-                        let is_item_ranged = is_ranged.get(item_entity);
-                        let is_player_teleporting = self.ecs.read_storage::<TeleportsPlayer>();
-                        if let Some(is_item_ranged) = is_item_ranged {
+                        let item_range = self
+                            .ecs
+                            .read_storage::<Ranged>()
+                            .get(item_entity)
+                            .map(|ranged| ranged.range);
+                        if let Some(range) = item_range {
+                            targeting::start_targeting(&mut self.ecs, range);
                             return RunState::ActionTargeting {
-                                range: is_item_ranged.range,
+                                range,
                                 item: item_entity,
                             };
-                        } else if let Some(is_player_teleporting) =
-                            is_player_teleporting.get(item_entity)
+                        }
+                        let is_player_teleporting = self.ecs.read_storage::<TeleportsPlayer>();
+                        if let Some(is_player_teleporting) = is_player_teleporting.get(item_entity)
                         {
                             return RunState::CoreNextLevel {
                                 level: is_player_teleporting.level,
@@ -204,6 +230,26 @@ impl State {
                     }
                 }
             }
+            RunState::MenuThrowItem => {
+                let result = gui::show_throw_item(self, ctx);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        let item_entity = result.1.expect(
+                            "show_throw_item always should return entity with Selected response",
+                        );
+                        let range = throw_system::throw_range(
+                            self.ecs.read_storage::<Weight>().get(item_entity),
+                        );
+                        targeting::start_targeting(&mut self.ecs, range);
+                        return RunState::ActionThrowing {
+                            range,
+                            item: item_entity,
+                        };
+                    }
+                }
+            }
             RunState::MenuRemoveItem => {
                 let result = gui::show_remove_item(self, ctx);
                 match result.0 {
@@ -225,23 +271,185 @@ impl State {
                 }
             }
 
+            RunState::MenuClearPath => {
+                let result = gui::clear_path_direction(self, ctx);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        let target = result.1.expect(
+                            "clear_path_direction always should return a tile with Selected response",
+                        );
+                        let mut intent = self.ecs.write_storage::<WantsToClearPath>();
+                        intent
+                            .insert(
+                                *self.ecs.fetch::<Entity>(),
+                                WantsToClearPath {
+                                    x: target.x,
+                                    y: target.y,
+                                },
+                            )
+                            .expect("should be able to insert intent to clear a path");
+                        return RunState::CorePlayerTurn;
+                    }
+                }
+            }
+            RunState::MenuShove => {
+                let result = gui::shove_direction(self, ctx);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        let target = result.1.expect(
+                            "shove_direction always should return a tile with Selected response",
+                        );
+                        let mut intent = self.ecs.write_storage::<WantsToShove>();
+                        intent
+                            .insert(
+                                *self.ecs.fetch::<Entity>(),
+                                WantsToShove {
+                                    x: target.x,
+                                    y: target.y,
+                                },
+                            )
+                            .expect("should be able to insert intent to shove");
+                        return RunState::CorePlayerTurn;
+                    }
+                }
+            }
+            RunState::MenuCrafting => {
+                let result = gui::show_crafting(self, ctx);
+                match result {
+                    gui::ItemMenuResult::Cancel => {
+                        self.ecs.fetch_mut::<CraftingSelection>().items.clear();
+                        return RunState::CoreAwaitingInput;
+                    }
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        if crafting::craft(&mut self.ecs) {
+                            return RunState::CorePlayerTurn;
+                        }
+                        return current_runstate;
+                    }
+                }
+            }
+
+            RunState::MenuDialogue { npc, node } => {
+                if ctx.key == Some(rltk::VirtualKeyCode::Tab)
+                    && self.ecs.read_storage::<Persona>().contains(npc)
+                {
+                    self.ecs.fetch_mut::<ConversationInput>().line.clear();
+                    return RunState::MenuConverse { npc };
+                }
+                let result = gui::show_dialogue(self, ctx, npc, node);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        let choice = result.1.expect(
+                            "show_dialogue always should return a choice with Selected response",
+                        );
+                        // talking takes a turn once the conversation is over
+                        match dialogue::choose(&mut self.ecs, npc, node, choice) {
+                            Some(next) => return RunState::MenuDialogue { npc, node: next },
+                            None => return RunState::CorePlayerTurn,
+                        }
+                    }
+                }
+            }
+
+            RunState::MenuBarter { vendor, wanted } => {
+                if wanted < 0 {
+                    if ctx.key == Some(rltk::VirtualKeyCode::Tab)
+                        && self.ecs.read_storage::<Persona>().contains(vendor)
+                    {
+                        self.ecs.fetch_mut::<ConversationInput>().line.clear();
+                        return RunState::MenuConverse { npc: vendor };
+                    }
+                    let result = gui::show_vendor_stock(self, ctx, vendor);
+                    match result.0 {
+                        gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                        gui::ItemMenuResult::NoResponse => return current_runstate,
+                        gui::ItemMenuResult::Selected => {
+                            let wanted = result.1.expect(
+                                "show_vendor_stock always should return stock with Selected response",
+                            );
+                            self.ecs.fetch_mut::<BarterOffer>().items.clear();
+                            return RunState::MenuBarter {
+                                vendor,
+                                wanted: wanted as i32,
+                            };
+                        }
+                    }
+                }
+                let result = gui::show_barter_offer(self, ctx, vendor, wanted as usize);
+                match result {
+                    gui::ItemMenuResult::Cancel => {
+                        self.ecs.fetch_mut::<BarterOffer>().items.clear();
+                        return RunState::MenuBarter { vendor, wanted: -1 };
+                    }
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        if trade::complete_trade(&mut self.ecs, vendor, wanted as usize) {
+                            return RunState::CorePlayerTurn;
+                        }
+                        return current_runstate;
+                    }
+                }
+            }
+            RunState::MenuConverse { npc } => {
+                let result = gui::show_converse(self, ctx, npc);
+                match result {
+                    gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        converse::send_line(&self.ecs, npc);
+                        return RunState::ActionAwaitReply { npc, frames: 0 };
+                    }
+                }
+            }
+
             // breakout action states
             RunState::ActionTargeting { range, item } => {
-                let result = gui::ranged_target(self, ctx, range);
+                let result = gui::ranged_target(self, ctx, range, Some(item));
                 match result.0 {
                     gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
                     gui::ItemMenuResult::NoResponse => return current_runstate,
                     gui::ItemMenuResult::Selected => {
+                        let target = result
+                            .1
+                            .map(|target| weather::wind_drift(&self.ecs, target));
                         let mut intent = self.ecs.write_storage::<WantsToUseItem>();
+                        intent
+                            .insert(*self.ecs.fetch::<Entity>(), WantsToUseItem { item, target })
+                            .expect("should be able to insert intent to use item");
+                        return RunState::CorePlayerTurn;
+                    }
+                }
+            }
+            RunState::ActionThrowing { range, item } => {
+                let result = gui::ranged_target(self, ctx, range, None);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => return RunState::CoreAwaitingInput,
+                    gui::ItemMenuResult::NoResponse => return current_runstate,
+                    gui::ItemMenuResult::Selected => {
+                        let target = weather::wind_drift(
+                            &self.ecs,
+                            result.1.expect(
+                                "ranged_target always should return a tile with Selected response",
+                            ),
+                        );
+                        let mut intent = self.ecs.write_storage::<WantsToThrow>();
                         intent
                             .insert(
                                 *self.ecs.fetch::<Entity>(),
-                                WantsToUseItem {
+                                WantsToThrow {
                                     item,
-                                    target: result.1,
+                                    x: target.x,
+                                    y: target.y,
                                 },
                             )
-                            .expect("should be able to insert intent to use item");
+                            .expect("should be able to insert intent to throw item");
                         return RunState::CorePlayerTurn;
                     }
                 }
@@ -281,6 +489,18 @@ impl State {
                 }
                 _ => return current_runstate,
             },
+            RunState::ActionAwaitReply { npc, frames } => {
+                gui::show_awaiting_reply(self, ctx, npc, frames);
+                let reply = window_fx::npc_reply();
+                if reply.is_some() || frames >= converse::REPLY_TIMEOUT_FRAMES {
+                    converse::finish(&mut self.ecs, npc, reply);
+                    return RunState::CorePlayerTurn;
+                }
+                return RunState::ActionAwaitReply {
+                    npc,
+                    frames: frames + 1,
+                };
+            }
             RunState::ActionHighlightObjects {} => {
                 match ctx.key {
                     Some(rltk::VirtualKeyCode::Back) => {
@@ -322,10 +542,11 @@ impl State {
                         }
                     }
                     gui::MainMenuResult::Selected { selected } => match selected {
-                        gui::MainMenuSelection::NewGame => return RunState::CoreLevelStart,
+                        gui::MainMenuSelection::NewGame => return RunState::OuterNameEntry,
                         gui::MainMenuSelection::LoadGame => {
                             saveload_system::load_game(&mut self.ecs);
                             saveload_system::delete_save();
+                            self.sync_player_name_from_save();
                             return RunState::CorePreRound;
                         }
                         gui::MainMenuSelection::Quit => {
@@ -334,6 +555,16 @@ impl State {
                     },
                 }
             }
+            RunState::OuterNameEntry => {
+                let result = menu::name_entry(self, ctx);
+                match result {
+                    gui::NameEntryResult::NoSelection => return current_runstate,
+                    gui::NameEntryResult::Selected => {
+                        self.apply_player_name();
+                        return RunState::CoreLevelStart;
+                    }
+                }
+            }
             RunState::OuterSaveGame => {
                 saveload_system::save_game(&mut self.ecs);
                 return RunState::OuterMainMenu {
@@ -78,6 +78,16 @@ impl GameLog {
         });
         read_entries.chain(unread_entries)
     }
+    pub fn recent(&self, count: usize) -> Vec<String> {
+        let mut recent: Vec<String> = self
+            .entries()
+            .rev()
+            .take(count)
+            .map(|e| e.entry.to_string())
+            .collect();
+        recent.reverse();
+        recent
+    }
     pub fn mark_all_read(&mut self) {
         let mut queue = vec![];
         while !self.unread.is_empty() {
@@ -3,13 +3,23 @@ use rltk::{Point, Rltk, VirtualKeyCode, RGB};
 use specs::prelude::*;
 
 use crate::{
+    clear_path_system::can_clear,
     components::{
-        Backpack, CombatStats, Equipped, Hidden, HighlightObject, HungerClock, HungerState,
-        InBackpack, Renderable, Viewshed,
+        AreaOfEffect, Backpack, CombatStats, Dialogue, Equipped, Hidden, HighlightObject,
+        HungerClock, HungerState, InBackpack, Item, Persona, Renderable, Sneaking, StatusEffects,
+        ThirstClock, ThirstState, Vendor, Viewshed, Worth,
     },
+    converse::{key_to_line_char, ConversationInput, MAX_PLAYER_LINE_LENGTH},
+    crafting::{known_recipes, player_items, select_recipe, toggle_selection, CraftingSelection},
+    day_night_system::{DayPhase, WorldClock},
+    dialogue::{available_choices, DialogueLibrary},
     gamelog::LogEntry,
     get_visible_tooltips,
+    name_mangler::fill_name,
     stats::OverallStats,
+    targeting::{cycle_target, move_cursor, remember_target, TargetingCursor},
+    trade::{offer_worth, toggle_offer, BarterOffer},
+    weather::Weather,
     RunState, State,
 };
 
@@ -26,6 +36,12 @@ pub enum MainMenuResult {
     Selected { selected: MainMenuSelection },
 }
 
+#[derive(PartialEq, Copy, Clone)]
+pub enum NameEntryResult {
+    NoSelection,
+    Selected,
+}
+
 pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
     // bg box
     ctx.draw_box(
@@ -160,6 +176,84 @@ pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
             ),
         }
     }
+    let thirst = ecs.read_storage::<ThirstClock>();
+    for (_player, tc) in (&players, &thirst).join() {
+        match tc.state {
+            ThirstState::Quenched => ctx.print_color(
+                59,
+                42,
+                RGB::from_hex("#70a0c0").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                "QUENCHED..",
+            ),
+            ThirstState::Normal => {}
+            ThirstState::Thirsty => ctx.print_color(
+                59,
+                42,
+                RGB::from_hex("#e0c080").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                "THIRSTY...",
+            ),
+            ThirstState::Parched => ctx.print_color(
+                59,
+                42,
+                RGB::from_hex("#e08080").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                "PARCHED!!!",
+            ),
+        }
+    }
+
+    // time of day
+    let clock = ecs.fetch::<WorldClock>();
+    ctx.print_color(
+        52,
+        42,
+        match clock.phase() {
+            DayPhase::Dawn => RGB::from_hex("#e0a080").expect("hardcoded"),
+            DayPhase::Day => RGB::from_hex("#f0e080").expect("hardcoded"),
+            DayPhase::Dusk => RGB::from_hex("#c07060").expect("hardcoded"),
+            DayPhase::Night => RGB::from_hex("#6070c0").expect("hardcoded"),
+        },
+        RGB::named(rltk::BLACK),
+        clock.phase_name(),
+    );
+    // status effects, as many as fit
+    if let Some(statuses) = ecs.read_storage::<StatusEffects>().get(*player_entity) {
+        let mut x = 2;
+        for effect in statuses.effects.iter() {
+            let label = effect.kind.name();
+            if x + label.len() as i32 > 28 {
+                break;
+            }
+            ctx.print_color(
+                x,
+                42,
+                RGB::from_hex(effect.kind.color()).expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                label,
+            );
+            x += label.len() as i32 + 1;
+        }
+    }
+    if ecs.read_storage::<Sneaking>().contains(*player_entity) {
+        ctx.print_color(
+            29,
+            42,
+            RGB::from_hex("#8080a0").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            "SNEAKING",
+        );
+    }
+    if map.weather != Weather::Clear {
+        ctx.print_color(
+            40,
+            42,
+            RGB::from_hex("#a0b0c0").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            map.weather.name(),
+        );
+    }
 
     // game log
     let log = ecs.fetch::<GameLog>();
@@ -463,6 +557,18 @@ pub fn show_inventory(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
 }
 
 pub fn show_drop_item(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
+    pick_backpack_item(gs, ctx, "DROP ITEM?")
+}
+
+pub fn show_throw_item(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
+    pick_backpack_item(gs, ctx, "THROW ITEM?")
+}
+
+fn pick_backpack_item(
+    gs: &mut State,
+    ctx: &mut Rltk,
+    title: &str,
+) -> (ItemMenuResult, Option<Entity>) {
     let player_entity = gs.ecs.fetch::<Entity>();
     let names = gs.ecs.read_storage::<Name>();
     let backpack = gs.ecs.read_storage::<InBackpack>();
@@ -487,7 +593,7 @@ pub fn show_drop_item(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
         y - 2,
         RGB::named(rltk::BURLYWOOD),
         RGB::named(rltk::BLACK),
-        "DROP ITEM?",
+        title,
     );
     ctx.print_color(
         18,
@@ -636,62 +742,826 @@ pub fn show_remove_item(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Opti
     }
 }
 
+pub fn show_dialogue(
+    gs: &mut State,
+    ctx: &mut Rltk,
+    npc: Entity,
+    node: usize,
+) -> (ItemMenuResult, Option<usize>) {
+    let library = gs.ecs.fetch::<DialogueLibrary>();
+    let dialogues = gs.ecs.read_storage::<Dialogue>();
+    let names = gs.ecs.read_storage::<Name>();
+    let stats = gs.ecs.fetch::<OverallStats>();
+
+    let npc_name = match names.get(npc) {
+        Some(name) => name.name.clone(),
+        None => return (ItemMenuResult::Cancel, None),
+    };
+    let dialogue_node = match dialogues
+        .get(npc)
+        .and_then(|dialogue| library.tree(&dialogue.tree))
+        .and_then(|tree| tree.nodes.get(node))
+    {
+        Some(dialogue_node) => dialogue_node,
+        None => return (ItemMenuResult::Cancel, None),
+    };
+    let lines = wrap_text(&fill_name(&dialogue_node.line, &stats, &npc_name), 44);
+    let choices = available_choices(&gs.ecs, npc, dialogue_node);
+    let count = choices.len();
+
+    let height = (lines.len() + count + 4) as i32;
+    let mut y = 22 - height / 2;
+    ctx.draw_box(
+        15,
+        y,
+        48,
+        height,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color(
+        18,
+        y,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        &npc_name,
+    );
+    ctx.print_color(
+        18,
+        y + height,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        "[BACKSPACE] to leave",
+    );
+    if gs.ecs.read_storage::<Persona>().contains(npc) {
+        ctx.print_color(
+            40,
+            y + height,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+            "[TAB] say something",
+        );
+    }
+
+    y += 2;
+    for line in lines.iter() {
+        ctx.print_color(
+            17,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            line,
+        );
+        y += 1;
+    }
+    y += 1;
+
+    for (j, choice) in choices.iter().enumerate() {
+        ctx.set(
+            17,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437('('),
+        );
+        ctx.set(
+            18,
+            y,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+            97 + j as rltk::FontCharType,
+        );
+        ctx.set(
+            19,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437(')'),
+        );
+        ctx.print_color(
+            21,
+            y,
+            RGB::from_hex("#a0a0a0").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            &choice.text,
+        );
+        y += 1;
+    }
+
+    match ctx.key {
+        None => (ItemMenuResult::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Back => (ItemMenuResult::Cancel, None),
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    return (ItemMenuResult::Selected, Some(selection as usize));
+                }
+                (ItemMenuResult::NoResponse, None)
+            }
+        },
+    }
+}
+
+pub fn show_converse(gs: &mut State, ctx: &mut Rltk, npc: Entity) -> ItemMenuResult {
+    let names = gs.ecs.read_storage::<Name>();
+    let mut input = gs.ecs.fetch_mut::<ConversationInput>();
+    let npc_name = match names.get(npc) {
+        Some(name) => name.name.clone(),
+        None => return ItemMenuResult::Cancel,
+    };
+
+    ctx.draw_box(
+        15,
+        18,
+        48,
+        6,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color(
+        18,
+        18,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        format!("say something to {}", npc_name),
+    );
+    ctx.print_color(
+        17,
+        21,
+        RGB::named(rltk::WHITE),
+        RGB::named(rltk::BLACK),
+        format!("> {}_", input.line),
+    );
+    ctx.print_color(
+        18,
+        24,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        "[ENTER] to say it",
+    );
+
+    match ctx.key {
+        None => ItemMenuResult::NoResponse,
+        Some(key) => match key {
+            VirtualKeyCode::Escape => ItemMenuResult::Cancel,
+            VirtualKeyCode::Back => {
+                // backing out of an empty line leaves the conversation
+                if input.line.pop().is_none() {
+                    return ItemMenuResult::Cancel;
+                }
+                ItemMenuResult::NoResponse
+            }
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                if input.line.trim().is_empty() {
+                    return ItemMenuResult::NoResponse;
+                }
+                ItemMenuResult::Selected
+            }
+            _ => {
+                if input.line.len() < MAX_PLAYER_LINE_LENGTH {
+                    if let Some(c) = key_to_line_char(key, ctx.shift) {
+                        input.line.push(c);
+                    }
+                }
+                ItemMenuResult::NoResponse
+            }
+        },
+    }
+}
+
+pub fn show_awaiting_reply(gs: &mut State, ctx: &mut Rltk, npc: Entity, frames: i32) {
+    let names = gs.ecs.read_storage::<Name>();
+    let npc_name = names
+        .get(npc)
+        .map(|name| name.name.clone())
+        .unwrap_or_default();
+    let dots = ".".repeat(1 + (frames / 20 % 3) as usize);
+
+    ctx.draw_box(
+        15,
+        20,
+        48,
+        2,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color(
+        17,
+        21,
+        RGB::from_hex("#a0a0a0").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+        format!("{} is thinking{}", npc_name, dots),
+    );
+}
+
+pub fn show_vendor_stock(
+    gs: &mut State,
+    ctx: &mut Rltk,
+    vendor: Entity,
+) -> (ItemMenuResult, Option<usize>) {
+    let names = gs.ecs.read_storage::<Name>();
+    let vendors = gs.ecs.read_storage::<Vendor>();
+    let (vendor_name, stock) = match (names.get(vendor), vendors.get(vendor)) {
+        (Some(name), Some(stock)) => (name.name.clone(), stock.stock.clone()),
+        _ => return (ItemMenuResult::Cancel, None),
+    };
+    let count = stock.len();
+
+    let mut y = (22 - (count / 2)) as i32;
+    ctx.draw_box(
+        15,
+        y - 2,
+        48,
+        (count + 3) as i32,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color(
+        18,
+        y - 2,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        format!("{} WILL TRADE", vendor_name),
+    );
+    ctx.print_color(
+        18,
+        y + count as i32 + 1,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        "[BACKSPACE] to leave",
+    );
+    if gs.ecs.read_storage::<Persona>().contains(vendor) {
+        ctx.print_color(
+            40,
+            y + count as i32 + 1,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+            "[TAB] say something",
+        );
+    }
+    if count == 0 {
+        ctx.print_color(
+            21,
+            y,
+            RGB::from_hex("#a0a0a0").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            "sold out!",
+        );
+    }
+
+    for (j, item) in stock.iter().enumerate() {
+        ctx.set(
+            17,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437('('),
+        );
+        ctx.set(
+            18,
+            y,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+            97 + j as rltk::FontCharType,
+        );
+        ctx.set(
+            19,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437(')'),
+        );
+        ctx.print_color(
+            21,
+            y,
+            RGB::from_hex("#a0a0a0").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            &item.name,
+        );
+        ctx.print_color(
+            52,
+            y,
+            RGB::from_hex("#e0c070").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            format!("worth {}", item.price),
+        );
+        y += 1;
+    }
+
+    match ctx.key {
+        None => (ItemMenuResult::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Back => (ItemMenuResult::Cancel, None),
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    return (ItemMenuResult::Selected, Some(selection as usize));
+                }
+                (ItemMenuResult::NoResponse, None)
+            }
+        },
+    }
+}
+
+pub fn show_barter_offer(
+    gs: &mut State,
+    ctx: &mut Rltk,
+    vendor: Entity,
+    wanted: usize,
+) -> ItemMenuResult {
+    let mut toggled = None;
+    let result;
+    {
+        let player_entity = gs.ecs.fetch::<Entity>();
+        let names = gs.ecs.read_storage::<Name>();
+        let backpack_items = gs.ecs.read_storage::<InBackpack>();
+        let worths = gs.ecs.read_storage::<Worth>();
+        let vendors = gs.ecs.read_storage::<Vendor>();
+        let entities = gs.ecs.entities();
+        let offer = gs.ecs.fetch::<BarterOffer>();
+
+        let wanted_item = match vendors
+            .get(vendor)
+            .and_then(|vendor| vendor.stock.get(wanted))
+        {
+            Some(wanted_item) => wanted_item.clone(),
+            None => return ItemMenuResult::Cancel,
+        };
+        let inventory: Vec<(Entity, String, i32)> = (&entities, &backpack_items, &names)
+            .join()
+            .filter(|item| item.1.owner == *player_entity)
+            .map(|(entity, _pack, name)| {
+                (
+                    entity,
+                    name.name.clone(),
+                    worths.get(entity).map(|worth| worth.value).unwrap_or(0),
+                )
+            })
+            .collect();
+        let count = inventory.len();
+        let offered_worth = offer_worth(&gs.ecs);
+
+        let mut y = (22 - (count / 2)) as i32;
+        ctx.draw_box(
+            15,
+            y - 4,
+            48,
+            (count + 5) as i32,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+        );
+        ctx.print_color(
+            18,
+            y - 4,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+            format!("OFFER FOR THE {}", wanted_item.name),
+        );
+        let worth_color = if offered_worth >= wanted_item.price {
+            RGB::from_hex("#70e0a0").expect("hardcoded")
+        } else {
+            RGB::from_hex("#e08080").expect("hardcoded")
+        };
+        ctx.print_color(
+            17,
+            y - 2,
+            worth_color,
+            RGB::named(rltk::BLACK),
+            format!("offered {} / asking {}", offered_worth, wanted_item.price),
+        );
+        ctx.print_color(
+            18,
+            y + count as i32 + 1,
+            RGB::named(rltk::BURLYWOOD),
+            RGB::named(rltk::BLACK),
+            "[ENTER] to trade [BACKSPACE] to go back",
+        );
+
+        for (j, (entity, name, worth)) in inventory.iter().enumerate() {
+            let offered = offer.items.contains(entity);
+            ctx.set(
+                17,
+                y,
+                RGB::named(rltk::WHITE),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437('('),
+            );
+            ctx.set(
+                18,
+                y,
+                RGB::named(rltk::BURLYWOOD),
+                RGB::named(rltk::BLACK),
+                97 + j as rltk::FontCharType,
+            );
+            ctx.set(
+                19,
+                y,
+                RGB::named(rltk::WHITE),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437(')'),
+            );
+            let name_color = if offered {
+                RGB::from_hex("#e0c070").expect("hardcoded")
+            } else {
+                RGB::from_hex("#a0a0a0").expect("hardcoded")
+            };
+            ctx.print_color(
+                21,
+                y,
+                name_color,
+                RGB::named(rltk::BLACK),
+                format!("{}{}", if offered { "+ " } else { "" }, name),
+            );
+            ctx.print_color(
+                52,
+                y,
+                RGB::from_hex("#e0c070").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                format!("worth {}", worth),
+            );
+            y += 1;
+        }
+
+        result = match ctx.key {
+            None => ItemMenuResult::NoResponse,
+            Some(key) => match key {
+                VirtualKeyCode::Back => ItemMenuResult::Cancel,
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => ItemMenuResult::Selected,
+                _ => {
+                    let selection = rltk::letter_to_option(key);
+                    if selection > -1 && selection < count as i32 {
+                        toggled = Some(inventory[selection as usize].0);
+                    }
+                    ItemMenuResult::NoResponse
+                }
+            },
+        };
+    }
+    if let Some(item) = toggled {
+        toggle_offer(&mut gs.ecs, item);
+    }
+    result
+}
+
+// greedy word wrap, so long npc lines fit in a box
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.len() + word.len() + 1 > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 pub fn ranged_target(
     gs: &mut State,
     ctx: &mut Rltk,
     range: i32,
+    item: Option<Entity>,
 ) -> (ItemMenuResult, Option<Point>) {
-    let player_entity = gs.ecs.fetch::<Entity>();
-    let player_pos = gs.ecs.fetch::<Point>();
-    let viewsheds = gs.ecs.read_storage::<Viewshed>();
+    let player_entity = *gs.ecs.fetch::<Entity>();
+    let player_pos = *gs.ecs.fetch::<Point>();
 
     ctx.print_color(
         5,
         0,
         RGB::from_hex("#a07030").expect("hardcoded"),
         RGB::named(rltk::BLACK),
-        "[CLICK] on target (or an empty space to cancel):",
+        "[direction] to aim, [TAB] next target, [SPACE] to fire (or ESC to cancel):",
     );
 
     let mut available_cells = Vec::new();
-    let visible = viewsheds.get(*player_entity);
-    if let Some(visible) = visible {
-        for idx in visible.visible_tiles.iter() {
-            let distance = rltk::DistanceAlg::Pythagoras.distance2d(*player_pos, *idx);
-            if distance <= range as f32 {
-                ctx.set_bg(idx.x, idx.y, RGB::from_hex("#204050").expect("hardcoded"));
-                available_cells.push(idx);
+    match gs.ecs.read_storage::<Viewshed>().get(player_entity) {
+        Some(visible) => {
+            for idx in visible.visible_tiles.iter() {
+                let distance = rltk::DistanceAlg::Pythagoras.distance2d(player_pos, *idx);
+                if distance <= range as f32 {
+                    ctx.set_bg(idx.x, idx.y, RGB::from_hex("#204050").expect("hardcoded"));
+                    available_cells.push(*idx);
+                }
             }
         }
-    } else {
-        return (ItemMenuResult::Cancel, None);
+        None => return (ItemMenuResult::Cancel, None),
     }
 
+    // the mouse still works, it just takes over the cursor when it moves
     let mouse_pos = ctx.mouse_pos();
-    let mut valid_target = false;
-    for idx in available_cells.iter() {
-        if idx.x == mouse_pos.0 && idx.y == mouse_pos.1 {
-            valid_target = true;
+    {
+        let mut cursor = gs.ecs.fetch_mut::<TargetingCursor>();
+        if cursor.mouse.map_or(false, |mouse| mouse != mouse_pos) {
+            cursor.pos = Point::new(mouse_pos.0, mouse_pos.1);
         }
+        cursor.mouse = Some(mouse_pos);
     }
+    let target = gs.ecs.fetch::<TargetingCursor>().pos;
+    let valid_target = available_cells.contains(&target);
+
     if valid_target {
-        ctx.set_bg(mouse_pos.0, mouse_pos.1, RGB::named(rltk::CYAN));
-        if ctx.left_click {
-            return (
-                ItemMenuResult::Selected,
-                Some(Point::new(mouse_pos.0, mouse_pos.1)),
-            );
+        for step in rltk::line2d(rltk::LineAlg::Bresenham, player_pos, target)
+            .iter()
+            .skip(1)
+        {
+            ctx.set_bg(step.x, step.y, RGB::from_hex("#406070").expect("hardcoded"));
         }
+        let radius = item.and_then(|item| {
+            gs.ecs
+                .read_storage::<AreaOfEffect>()
+                .get(item)
+                .map(|area_effect| area_effect.radius)
+        });
+        if let Some(radius) = radius {
+            let map = gs.ecs.fetch::<Map>();
+            for tile in rltk::field_of_view(target, radius, &*map) {
+                if tile.x > 0 && tile.x < map.width - 1 && tile.y > 0 && tile.y < map.height - 1 {
+                    ctx.set_bg(tile.x, tile.y, RGB::from_hex("#703020").expect("hardcoded"));
+                }
+            }
+        }
+        ctx.set_bg(target.x, target.y, RGB::named(rltk::CYAN));
     } else {
-        ctx.set_bg(mouse_pos.0, mouse_pos.1, RGB::named(rltk::RED));
-        if ctx.left_click {
+        ctx.set_bg(target.x, target.y, RGB::named(rltk::RED));
+    }
+
+    // a click (or tap) always means the tile under the pointer
+    if ctx.left_click {
+        let clicked = Point::new(mouse_pos.0, mouse_pos.1);
+        if !available_cells.contains(&clicked) {
             return (ItemMenuResult::Cancel, None);
         }
+        remember_target(&mut gs.ecs, clicked);
+        return (ItemMenuResult::Selected, Some(clicked));
     }
 
+    let (dx, dy) = match ctx.key {
+        None => return (ItemMenuResult::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Escape | VirtualKeyCode::Back => return (ItemMenuResult::Cancel, None),
+            VirtualKeyCode::Space | VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                if !valid_target {
+                    return (ItemMenuResult::NoResponse, None);
+                }
+                remember_target(&mut gs.ecs, target);
+                return (ItemMenuResult::Selected, Some(target));
+            }
+            VirtualKeyCode::Tab => {
+                cycle_target(&mut gs.ecs, range);
+                return (ItemMenuResult::NoResponse, None);
+            }
+            _ => match direction_key(key) {
+                Some(delta) => delta,
+                None => return (ItemMenuResult::NoResponse, None),
+            },
+        },
+    };
+    move_cursor(&mut gs.ecs, dx, dy);
     (ItemMenuResult::NoResponse, None)
 }
 
+pub fn clear_path_direction(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Point>) {
+    let player_pos = *gs.ecs.fetch::<Point>();
+    let map = gs.ecs.fetch::<Map>();
+
+    ctx.print_color(
+        5,
+        0,
+        RGB::from_hex("#a07030").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+        "which tree? [direction] to start cutting (or ESC to cancel):",
+    );
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let (x, y) = (player_pos.x + dx, player_pos.y + dy);
+            if can_clear(&map, x, y) {
+                ctx.set_bg(x, y, RGB::from_hex("#204050").expect("hardcoded"));
+            }
+        }
+    }
+
+    let (dx, dy) = match ctx.key {
+        None => return (ItemMenuResult::NoResponse, None),
+        Some(VirtualKeyCode::Escape) => return (ItemMenuResult::Cancel, None),
+        Some(key) => match direction_key(key) {
+            Some(delta) => delta,
+            None => return (ItemMenuResult::NoResponse, None),
+        },
+    };
+    let target = Point::new(player_pos.x + dx, player_pos.y + dy);
+    if !can_clear(&map, target.x, target.y) {
+        return (ItemMenuResult::Cancel, None);
+    }
+    (ItemMenuResult::Selected, Some(target))
+}
+
+fn direction_key(key: VirtualKeyCode) -> Option<(i32, i32)> {
+    match key {
+        VirtualKeyCode::Left | VirtualKeyCode::Numpad4 | VirtualKeyCode::H => Some((-1, 0)),
+        VirtualKeyCode::Right | VirtualKeyCode::Numpad6 | VirtualKeyCode::L => Some((1, 0)),
+        VirtualKeyCode::Up | VirtualKeyCode::Numpad8 | VirtualKeyCode::K => Some((0, -1)),
+        VirtualKeyCode::Down | VirtualKeyCode::Numpad2 | VirtualKeyCode::J => Some((0, 1)),
+        VirtualKeyCode::Numpad9 | VirtualKeyCode::U => Some((1, -1)),
+        VirtualKeyCode::Numpad7 | VirtualKeyCode::Y => Some((-1, -1)),
+        VirtualKeyCode::Numpad3 | VirtualKeyCode::B => Some((1, 1)),
+        VirtualKeyCode::Numpad1 | VirtualKeyCode::N => Some((-1, 1)),
+        _ => None,
+    }
+}
+
+pub fn shove_direction(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Point>) {
+    let player_pos = *gs.ecs.fetch::<Point>();
+    let map = gs.ecs.fetch::<Map>();
+    let combat_stats = gs.ecs.read_storage::<CombatStats>();
+    let items = gs.ecs.read_storage::<Item>();
+    let shoveable = |x: i32, y: i32| {
+        (x, y) != (player_pos.x, player_pos.y)
+            && map.tile_content[map.xy_idx(x, y)]
+                .iter()
+                .any(|e| combat_stats.contains(*e) || items.contains(*e))
+    };
+
+    ctx.print_color(
+        5,
+        0,
+        RGB::from_hex("#a07030").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+        "shove which way? [direction] (or ESC to cancel):",
+    );
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let (x, y) = (player_pos.x + dx, player_pos.y + dy);
+            if shoveable(x, y) {
+                ctx.set_bg(x, y, RGB::from_hex("#204050").expect("hardcoded"));
+            }
+        }
+    }
+
+    let (dx, dy) = match ctx.key {
+        None => return (ItemMenuResult::NoResponse, None),
+        Some(VirtualKeyCode::Escape) => return (ItemMenuResult::Cancel, None),
+        Some(key) => match direction_key(key) {
+            Some(delta) => delta,
+            None => return (ItemMenuResult::NoResponse, None),
+        },
+    };
+    let target = Point::new(player_pos.x + dx, player_pos.y + dy);
+    if !shoveable(target.x, target.y) {
+        return (ItemMenuResult::Cancel, None);
+    }
+    (ItemMenuResult::Selected, Some(target))
+}
+
+pub fn show_crafting(gs: &mut State, ctx: &mut Rltk) -> ItemMenuResult {
+    let inventory = player_items(&gs.ecs);
+    let known = known_recipes(&gs.ecs);
+    let count = inventory.len();
+    let rows = usize::max(count, known.len() + 2);
+    let mut toggled = None;
+    let mut picked = None;
+    {
+        let selection = gs.ecs.fetch::<CraftingSelection>();
+        let mut y = (22 - (rows / 2)) as i32;
+        ctx.draw_box(
+            10,
+            y - 2,
+            60,
+            (rows + 3) as i32,
+            RGB::from_hex("#a07030").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+        );
+        ctx.print_color(
+            13,
+            y - 2,
+            RGB::from_hex("#a07030").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            "CRAFTING",
+        );
+        ctx.print_color(
+            13,
+            y + rows as i32 + 1,
+            RGB::from_hex("#a07030").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            "[ENTER] to combine [ESC] to cancel",
+        );
+
+        ctx.print_color(
+            46,
+            y,
+            RGB::from_hex("#e0c070").expect("hardcoded"),
+            RGB::named(rltk::BLACK),
+            "known recipes:",
+        );
+        if known.is_empty() {
+            ctx.print_color(
+                46,
+                y + 1,
+                RGB::from_hex("#a0a0a0").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                "none yet, experiment!",
+            );
+        }
+        for (j, recipe) in known.iter().take(9).enumerate() {
+            ctx.print_color(
+                46,
+                y + 1 + j as i32,
+                RGB::from_hex("#a0a0a0").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                format!("({}) {}", j + 1, recipe),
+            );
+        }
+
+        for (j, (entity, name)) in inventory.iter().enumerate() {
+            let selected = selection.items.contains(entity);
+            ctx.set(
+                12,
+                y,
+                RGB::named(rltk::WHITE),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437('('),
+            );
+            ctx.set(
+                13,
+                y,
+                RGB::from_hex("#a07030").expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                97 + j as rltk::FontCharType,
+            );
+            ctx.set(
+                14,
+                y,
+                RGB::named(rltk::WHITE),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437(')'),
+            );
+            let name_color = if selected {
+                RGB::from_hex("#e0c070").expect("hardcoded")
+            } else {
+                RGB::from_hex("#a0a0a0").expect("hardcoded")
+            };
+            ctx.print_color(
+                16,
+                y,
+                name_color,
+                RGB::named(rltk::BLACK),
+                format!("{}{}", if selected { "+ " } else { "" }, name),
+            );
+            y += 1;
+        }
+    }
+
+    let result = match ctx.key {
+        None => ItemMenuResult::NoResponse,
+        Some(key) => match key {
+            VirtualKeyCode::Escape => ItemMenuResult::Cancel,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => ItemMenuResult::Selected,
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    toggled = Some(inventory[selection as usize].0);
+                }
+                let number = match key {
+                    VirtualKeyCode::Key1 => Some(0),
+                    VirtualKeyCode::Key2 => Some(1),
+                    VirtualKeyCode::Key3 => Some(2),
+                    VirtualKeyCode::Key4 => Some(3),
+                    VirtualKeyCode::Key5 => Some(4),
+                    VirtualKeyCode::Key6 => Some(5),
+                    VirtualKeyCode::Key7 => Some(6),
+                    VirtualKeyCode::Key8 => Some(7),
+                    VirtualKeyCode::Key9 => Some(8),
+                    _ => None,
+                };
+                picked = number.and_then(|n| known.get(n).cloned());
+                ItemMenuResult::NoResponse
+            }
+        },
+    };
+    if let Some(item) = toggled {
+        toggle_selection(&mut gs.ecs, item);
+    }
+    if let Some(recipe) = picked {
+        if !select_recipe(&mut gs.ecs, &recipe) {
+            gs.ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                notification: format!(
+                    "{} doesn't have everything for {}.",
+                    gs.ecs.fetch::<OverallStats>().name,
+                    recipe
+                ),
+            });
+        }
+    }
+    result
+}
+
 #[derive(PartialEq, Copy, Clone)]
 pub enum GameOverResult {
     NoSelection,
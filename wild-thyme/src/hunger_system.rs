@@ -1,9 +1,12 @@
 use specs::prelude::*;
 
 use crate::{
-    components::{HungerClock, HungerState, Position, SufferDamage},
+    components::{Equipped, HungerClock, HungerState, Position, SufferDamage, Warm},
     gamelog::{GameLog, LogEntry},
+    map::Map,
     particle_system::ParticleBuilder,
+    stats::{LevelStats, OverallStats},
+    weather::Weather,
     window_fx, RunState,
 };
 
@@ -19,6 +22,11 @@ impl<'a> System<'a> for HungerSystem {
         WriteExpect<'a, GameLog>,
         ReadStorage<'a, Position>,
         WriteExpect<'a, ParticleBuilder>,
+        ReadExpect<'a, Map>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, Warm>,
+        WriteExpect<'a, LevelStats>,
+        ReadExpect<'a, OverallStats>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -31,8 +39,19 @@ impl<'a> System<'a> for HungerSystem {
             mut log,
             positions,
             mut particle_builder,
+            map,
+            equipped,
+            warm,
+            mut level_stats,
+            stats,
         ) = data;
 
+        // the cold burns through the player's food unless they're bundled up
+        let player_is_cold = map.weather == Weather::ColdSnap
+            && !(&equipped, &warm)
+                .join()
+                .any(|(eq, _warm)| eq.owner == *player_entity);
+
         for (entity, mut clock) in (&entities, &mut hunger_clock).join() {
             let mut proceed = false;
 
@@ -52,6 +71,10 @@ impl<'a> System<'a> for HungerSystem {
 
             if proceed {
                 clock.duration -= 1;
+                if entity == *player_entity && player_is_cold {
+                    clock.duration -= 1;
+                    level_stats.cold_steps += 1;
+                }
                 if clock.duration < 1 {
                     match clock.state {
                         HungerState::Full => {
@@ -59,7 +82,7 @@ impl<'a> System<'a> for HungerSystem {
                             clock.duration = 200;
                             if entity == *player_entity {
                                 log.log(LogEntry::Notification {
-                                    notification: "YOU are no longer well fed.".to_string(),
+                                    notification: format!("{} is no longer well fed.", stats.name),
                                 });
                             }
                         }
@@ -68,7 +91,7 @@ impl<'a> System<'a> for HungerSystem {
                             clock.duration = 200;
                             if entity == *player_entity {
                                 log.log(LogEntry::Notification {
-                                    notification: "YOU are hungry.".to_string(),
+                                    notification: format!("{} is hungry.", stats.name),
                                 });
                             }
                         }
@@ -76,7 +99,7 @@ impl<'a> System<'a> for HungerSystem {
                             clock.state = HungerState::Starving;
                             if entity == *player_entity {
                                 log.log(LogEntry::Alert {
-                                    alert: "YOU are STARVING!".to_string(),
+                                    alert: format!("{} is STARVING!", stats.name),
                                 });
                                 let pos = positions
                                     .get(*player_entity)
@@ -95,7 +118,7 @@ impl<'a> System<'a> for HungerSystem {
                         HungerState::Starving => {
                             if entity == *player_entity {
                                 log.log(LogEntry::Alert {
-                                    alert: "YOU feel pain from the hunger D:".to_string(),
+                                    alert: format!("{} feels pain from the hunger D:", stats.name),
                                 });
                                 let pos = positions
                                     .get(*player_entity)
@@ -0,0 +1,93 @@
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        HungerClock, HungerState, Initiative, Monster, Position, StatusEffects, StatusKind,
+        Viewshed,
+    },
+    RunState,
+};
+
+pub const ACTION_COST: i32 = 100;
+pub const NORMAL_SPEED: i32 = 100;
+// nobody gets to bank more than a couple of turns
+const MAX_ENERGY: i32 = ACTION_COST * 2;
+
+// base speed after hunger and status effects
+pub fn effective_speed(
+    initiative: &Initiative,
+    hunger: Option<&HungerClock>,
+    statuses: Option<&StatusEffects>,
+) -> i32 {
+    let mut speed = initiative.speed;
+    speed += match hunger.map(|clock| clock.state) {
+        Some(HungerState::Full) => 10,
+        Some(HungerState::Hungry) => -10,
+        Some(HungerState::Starving) => -25,
+        _ => 0,
+    };
+    if let Some(statuses) = statuses {
+        if statuses.has(StatusKind::Slowed) {
+            speed /= 2;
+        }
+        if statuses.has(StatusKind::Hasted) {
+            speed = speed * 3 / 2;
+        }
+    }
+    i32::max(10, speed)
+}
+
+// whether anyone still has enough energy left for another go this round
+pub fn anyone_ready(ecs: &World) -> bool {
+    let initiatives = ecs.read_storage::<Initiative>();
+    let monsters = ecs.read_storage::<Monster>();
+    let positions = ecs.read_storage::<Position>();
+    let viewsheds = ecs.read_storage::<Viewshed>();
+    (&initiatives, &monsters, &positions, &viewsheds)
+        .join()
+        .any(|(initiative, _, _, _)| initiative.energy >= ACTION_COST)
+}
+
+// the player always gets one turn a round, so everyone else's pace is measured against theirs
+pub struct InitiativeSystem {}
+
+impl<'a> System<'a> for InitiativeSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Entity>,
+        WriteStorage<'a, Initiative>,
+        ReadStorage<'a, HungerClock>,
+        ReadStorage<'a, StatusEffects>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, runstate, player_entity, mut initiatives, hunger_clocks, statuses) = data;
+
+        if *runstate != RunState::CoreMonsterTurn {
+            return;
+        }
+
+        let player_speed = initiatives
+            .get(*player_entity)
+            .map_or(NORMAL_SPEED, |initiative| {
+                effective_speed(
+                    initiative,
+                    hunger_clocks.get(*player_entity),
+                    statuses.get(*player_entity),
+                )
+            });
+
+        for (entity, initiative) in (&entities, &mut initiatives).join() {
+            if entity == *player_entity {
+                continue;
+            }
+            let speed =
+                effective_speed(initiative, hunger_clocks.get(entity), statuses.get(entity));
+            initiative.energy = i32::min(
+                MAX_ENERGY,
+                initiative.energy + speed * NORMAL_SPEED / player_speed,
+            );
+        }
+    }
+}
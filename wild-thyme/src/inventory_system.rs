@@ -1,9 +1,8 @@
 use crate::{
     components::{
-        AreaOfEffect, Backpack, CombatStats, Confusion, Consumable, Equippable, Equipped,
-        GoodThyme, HungerClock, HungerState, InflictsDamage, MagicMapper, ProvidesFood,
-        ProvidesHealing, SufferDamage, TeleportsPlayer, WantsToDropItem, WantsToRemoveItem,
-        WantsToUseItem,
+        AreaOfEffect, Backpack, Bait, CombatStats, Consumable, Equippable, Equipped, GoodThyme,
+        HungerClock, HungerState, InflictsDamage, MagicMapper, ProvidesFood, ProvidesHealing,
+        SufferDamage, TeleportsPlayer, WantsToDropItem, WantsToRemoveItem, WantsToUseItem,
     },
     gamelog::LogEntry,
     map::Map,
@@ -28,6 +27,7 @@ impl<'a> System<'a> for ItemCollectionSystem {
         WriteStorage<'a, Backpack>,
         WriteExpect<'a, OverallStats>,
         WriteExpect<'a, ParticleBuilder>,
+        WriteStorage<'a, Bait>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -41,6 +41,7 @@ impl<'a> System<'a> for ItemCollectionSystem {
             mut backpacks,
             mut stats,
             mut particle_builder,
+            mut baits,
         ) = data;
 
         for pickup in wants_pickup.join() {
@@ -54,6 +55,8 @@ impl<'a> System<'a> for ItemCollectionSystem {
             }
             if !backpack_too_full {
                 positions.remove(pickup.item);
+                // only a freshly thrown snack lures anything in
+                baits.remove(pickup.item);
                 backpack_items
                     .insert(
                         pickup.item,
@@ -68,7 +71,9 @@ impl<'a> System<'a> for ItemCollectionSystem {
                 if backpack_too_full {
                     gamelog.log(LogEntry::Alert {
                         alert: format!(
-                            "YOUR backpack is full! YOU can't pick up the {}.",
+                            "{}'s backpack is full! {} can't pick up the {}.",
+                            stats.name,
+                            stats.name,
                             names
                                 .get(pickup.item)
                                 .expect("items should always have Name")
@@ -88,8 +93,8 @@ impl<'a> System<'a> for ItemCollectionSystem {
                     );
                 } else {
                     gamelog.log(LogEntry::Action {
-                        subject: format!("YOU"),
-                        verb: format!("pick up the"),
+                        subject: stats.name.clone(),
+                        verb: format!("picks up the"),
                         object: format!(
                             "{}",
                             names
@@ -127,7 +132,6 @@ impl<'a> System<'a> for UseItemSystem {
         ReadStorage<'a, AreaOfEffect>,
         ReadStorage<'a, ProvidesFood>,
         ReadStorage<'a, MagicMapper>,
-        WriteStorage<'a, Confusion>,
         WriteStorage<'a, CombatStats>,
         WriteStorage<'a, SufferDamage>,
         WriteStorage<'a, HungerClock>,
@@ -157,7 +161,6 @@ impl<'a> System<'a> for UseItemSystem {
             aoe,
             provides_food,
             magic_mapper,
-            mut confusion,
             mut combat_stats,
             mut suffer_damage,
             mut hunger_clocks,
@@ -173,6 +176,10 @@ impl<'a> System<'a> for UseItemSystem {
             good_thyme,
             mut level_stats,
         ) = data;
+        let player_name = &names
+            .get(*player_entity)
+            .expect("player should always have Name")
+            .name;
 
         for (entity, used_item, stats) in (&entities, &wants_use, &mut combat_stats).join() {
             let mut item_was_used = true;
@@ -231,8 +238,8 @@ impl<'a> System<'a> for UseItemSystem {
                         to_unequip.push(item_entity);
                         if target == *player_entity {
                             gamelog.log(LogEntry::Action {
-                                subject: format!("YOU"),
-                                verb: format!("unequip"),
+                                subject: player_name.clone(),
+                                verb: format!("unequips"),
                                 object: format!("{}", name.name),
                                 suffix: format!("."),
                             });
@@ -265,8 +272,8 @@ impl<'a> System<'a> for UseItemSystem {
                 backpack_items.remove(used_item.item);
                 if target == *player_entity {
                     gamelog.log(LogEntry::Action {
-                        subject: format!("YOU"),
-                        verb: format!("equip"),
+                        subject: player_name.clone(),
+                        verb: format!("equips"),
                         object: format!(
                             "{}",
                             names
@@ -286,12 +293,18 @@ impl<'a> System<'a> for UseItemSystem {
                 Some(healer) => {
                     stats.hp = i32::min(stats.max_hp, stats.hp + healer.heal_amount);
                     if entity == *player_entity {
+                        let item_name = &names.get(used_item.item).unwrap().name;
                         gamelog.log(LogEntry::Alert {
-                            alert: format!(
-                                "The {} healed {} hp!",
-                                names.get(used_item.item).unwrap().name,
-                                healer.heal_amount
-                            ),
+                            alert: match healer.heal_amount < 0 {
+                                // rotten food (and some mushrooms) do more harm than good
+                                true => format!(
+                                    "The {} made {} sick! {} hp",
+                                    item_name, player_name, healer.heal_amount
+                                ),
+                                false => {
+                                    format!("The {} healed {} hp!", item_name, healer.heal_amount)
+                                }
+                            },
                         });
                     }
                     item_was_used = true;
@@ -323,7 +336,7 @@ impl<'a> System<'a> for UseItemSystem {
                             let item_name =
                                 names.get(used_item.item).expect("items should have name");
                             gamelog.log(LogEntry::Action {
-                                subject: format!("YOU"),
+                                subject: player_name.clone(),
                                 verb: format!("used {} on", item_name.name),
                                 object: format!("{}", mob_name.name),
                                 suffix: format!(", {} damage!", damage.damage),
@@ -347,49 +360,6 @@ impl<'a> System<'a> for UseItemSystem {
                 }
             }
 
-            // confusing items
-            let mut add_confusion = Vec::new();
-            {
-                let causes_confusion = confusion.get(used_item.item);
-                match causes_confusion {
-                    None => {}
-                    Some(confusion) => {
-                        item_was_used = false;
-                        for mob in targets.iter().filter(|&m| names.contains(*m)) {
-                            add_confusion.push((*mob, confusion.turns));
-                            if entity == *player_entity {
-                                let mob_name = names.get(*mob).expect("targets should have a name");
-                                let item_name =
-                                    names.get(used_item.item).expect("items should have a name");
-                                gamelog.log(LogEntry::Action {
-                                    subject: format!("YOU"),
-                                    verb: format!("used {} on", item_name.name),
-                                    object: format!("{}", mob_name.name),
-                                    suffix: format!(", and it is CONFUSED!"),
-                                });
-                            }
-
-                            let pos = positions.get(*mob);
-                            if let Some(pos) = pos {
-                                particle_builder.request(
-                                    pos.x,
-                                    pos.y,
-                                    rltk::RGB::named(rltk::PURPLE),
-                                    rltk::RGB::named(rltk::BLACK),
-                                    rltk::to_cp437('?'),
-                                    200.0,
-                                );
-                            }
-                        }
-                    }
-                }
-            }
-            for mob in add_confusion.iter() {
-                confusion
-                    .insert(mob.0, Confusion { turns: mob.1 })
-                    .expect("should be able to insert Confusion");
-            }
-
             // edible items
             let item_edible = provides_food.get(used_item.item);
             match item_edible {
@@ -403,7 +373,8 @@ impl<'a> System<'a> for UseItemSystem {
                         if entity == *player_entity {
                             gamelog.log(LogEntry::Notification {
                                 notification: format!(
-                                    "YOU feel satisfied and full after eating the {}",
+                                    "{} feels satisfied and full after eating the {}",
+                                    player_name,
                                     names.get(used_item.item).unwrap().name
                                 ),
                             });
@@ -430,7 +401,7 @@ impl<'a> System<'a> for UseItemSystem {
             let item_maps = magic_mapper.get(used_item.item);
             if let Some(_item_maps) = item_maps {
                 gamelog.log(LogEntry::Alert {
-                    alert: format!("YOU can now SEE the trail!"),
+                    alert: format!("{} can now SEE the trail!", player_name),
                 });
                 item_was_used = true;
                 *runstate = RunState::ActionMagicMapReveal {
@@ -454,7 +425,7 @@ impl<'a> System<'a> for UseItemSystem {
             // teleporting items
             if let Some(_) = teleports_player.get(used_item.item) {
                 gamelog.log(LogEntry::Alert {
-                    alert: format!("YOU are carried to another level!"),
+                    alert: format!("{} is carried to another level!", player_name),
                 });
                 item_was_used = true;
 
@@ -545,7 +516,7 @@ impl<'a> System<'a> for ItemDropSystem {
 
             if entity == *player_entity {
                 gamelog.log(LogEntry::Action {
-                    subject: format!("YOU"),
+                    subject: format!("{}", names.get(entity).unwrap().name),
                     object: format!("{}", names.get(to_drop.item).unwrap().name),
                     verb: format!("dropped the"),
                     suffix: format!(".."),
@@ -582,7 +553,10 @@ impl<'a> System<'a> for ItemRemoveSystem {
             names,
         ) = data;
 
-        for (entity, to_remove, name) in (&entities, &wants_remove, &names).join() {
+        for (entity, to_remove) in (&entities, &wants_remove).join() {
+            let name = names
+                .get(to_remove.item)
+                .expect("items should always have Name");
             let mut backpack_too_full = false;
             if let Some(backpack) = backpacks.get_mut(entity) {
                 if backpack.items >= backpack.capacity {
@@ -600,14 +574,18 @@ impl<'a> System<'a> for ItemRemoveSystem {
             if entity == *player_entity {
                 if !backpack_too_full {
                     gamelog.log(LogEntry::Action {
-                        subject: format!("YOU"),
+                        subject: format!("{}", names.get(entity).unwrap().name),
                         object: format!("{}", name.name),
                         verb: format!("unequipped the"),
                         suffix: format!("."),
                     });
                 } else {
                     gamelog.log(LogEntry::Alert {
-                        alert: format!("YOUR backpack is full! can't unequip the {}", name.name),
+                        alert: format!(
+                            "{}'s backpack is full! can't unequip the {}",
+                            names.get(entity).unwrap().name,
+                            name.name
+                        ),
                     });
                 }
             }
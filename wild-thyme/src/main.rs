@@ -13,7 +13,12 @@ use map::*;
 mod player;
 mod rect;
 use rect::*;
+mod boss_system;
+mod clear_path_system;
 mod components;
+mod converse;
+mod day_night_system;
+mod dialogue;
 mod stats;
 use components::*;
 use stats::*;
@@ -22,18 +27,34 @@ mod gui;
 mod inventory_system;
 mod spawners;
 use inventory_system::*;
+mod crafting;
 mod discovery_system;
+mod ecology_system;
+mod fire;
+mod fog;
+mod foraging;
 mod game_loop;
 mod hunger_system;
+mod initiative_system;
 mod menu;
+mod name_mangler;
+mod noise_system;
 mod particle_system;
 mod quip_system;
 mod random_table;
 mod rex_assets;
 mod saveload_system;
+mod shove_system;
 mod spawn_system;
+mod spoilage_system;
 mod stats_system;
+mod status_system;
+mod targeting;
+mod thirst_system;
+mod throw_system;
+mod trade;
 mod trigger_system;
+mod weather;
 mod window_fx;
 
 pub mod map_builders;
@@ -46,6 +67,8 @@ mod map_indexing_system;
 use map_indexing_system::MapIndexingSystem;
 mod melee_combat_system;
 use melee_combat_system::MeleeCombatSystem;
+mod ranged_combat_system;
+use ranged_combat_system::RangedCombatSystem;
 mod damage_system;
 use damage_system::*;
 
@@ -57,6 +80,8 @@ pub enum RunState {
     CoreAwaitingInput,
     CorePlayerTurn,
     CoreMonsterTurn,
+    // fast mobs with energy to spare get another go before the round ends
+    CoreMonsterExtraTurn,
     CorePostRound,
 
     CoreFadeToNextLevel {
@@ -69,12 +94,31 @@ pub enum RunState {
 
     MenuInventory,
     MenuDropItem,
+    MenuThrowItem,
     MenuRemoveItem,
+    MenuClearPath,
+    MenuShove,
+    MenuCrafting,
+    MenuDialogue {
+        npc: Entity,
+        node: usize,
+    },
+    MenuConverse {
+        npc: Entity,
+    },
+    MenuBarter {
+        vendor: Entity,
+        wanted: i32,
+    },
 
     ActionTargeting {
         range: i32,
         item: Entity,
     },
+    ActionThrowing {
+        range: i32,
+        item: Entity,
+    },
     ActionMagicMapReveal {
         row: i32,
         iteration: i32,
@@ -84,10 +128,15 @@ pub enum RunState {
         total: i32,
     },
     ActionHighlightObjects {},
+    ActionAwaitReply {
+        npc: Entity,
+        frames: i32,
+    },
 
     OuterMainMenu {
         menu_selection: gui::MainMenuSelection,
     },
+    OuterNameEntry,
     OuterSaveGame,
     OuterCakeReveal {
         row: i32,
@@ -113,7 +162,7 @@ impl GameState for State {
 
         // render map if game is active
         match current_runstate {
-            RunState::OuterMainMenu { .. } => {}
+            RunState::OuterMainMenu { .. } | RunState::OuterNameEntry => {}
             _ => {
                 draw_map(&self.ecs, ctx);
 
@@ -187,6 +236,8 @@ impl State {
     fn run_systems(&mut self) {
         let mut vis = VisibilitySystem {};
         vis.run_now(&self.ecs);
+        ecology_system::EcologySystem {}.run_now(&self.ecs);
+        initiative_system::InitiativeSystem {}.run_now(&self.ecs);
         let mut mob = MonsterAI {};
         mob.run_now(&self.ecs);
         let mut triggers = trigger_system::TriggerSystem {};
@@ -195,10 +246,25 @@ impl State {
         mapindex.run_now(&self.ecs);
         let mut melee = MeleeCombatSystem {};
         melee.run_now(&self.ecs);
+        shove_system::ShoveSystem {}.run_now(&self.ecs);
+        shove_system::KnockbackSystem {}.run_now(&self.ecs);
+        throw_system::ThrowSystem {}.run_now(&self.ecs);
+        // whatever got knocked or thrown onto a trap sets it off right away
+        triggers.run_now(&self.ecs);
+        clear_path_system::ClearPathSystem {}.run_now(&self.ecs);
+        fire::FireSystem {}.run_now(&self.ecs);
+        let mut ranged = RangedCombatSystem {};
+        ranged.run_now(&self.ecs);
+        status_system::StatusSystem {}.run_now(&self.ecs);
         let mut damage = DamageSystem {};
         damage.run_now(&self.ecs);
+        boss_system::WizardBossSystem {}.run_now(&self.ecs);
+        noise_system::NoiseSystem {}.run_now(&self.ecs);
         let mut pickup = ItemCollectionSystem {};
         pickup.run_now(&self.ecs);
+        thirst_system::WaterUseSystem {}.run_now(&self.ecs);
+        fire::IgniteSystem {}.run_now(&self.ecs);
+        status_system::StatusUseSystem {}.run_now(&self.ecs);
         let mut potions = UseItemSystem {};
         potions.run_now(&self.ecs);
         let mut drop_items = ItemDropSystem {};
@@ -207,9 +273,13 @@ impl State {
         remove_items.run_now(&self.ecs);
         let mut hunger = HungerSystem {};
         hunger.run_now(&self.ecs);
+        thirst_system::ThirstSystem {}.run_now(&self.ecs);
+        day_night_system::DayNightSystem {}.run_now(&self.ecs);
+        spoilage_system::SpoilageSystem {}.run_now(&self.ecs);
         quip_system::QuipSystem {}.run_now(&self.ecs);
-        // let mut fog = fog::FogSystem {};
-        // fog.run_now(&self.ecs);
+        let mut fog = fog::FogSystem {};
+        fog.run_now(&self.ecs);
+        weather::WeatherSystem {}.run_now(&self.ecs);
         let mut particles = particle_system::ParticleSpawnSystem {};
         particles.run_now(&self.ecs);
 
@@ -269,6 +339,8 @@ impl State {
         }
 
         self.generate_world_map(level);
+        fog::spawn_fog_bank(&mut self.ecs);
+        weather::start_weather(&mut self.ecs);
 
         let player_entity = self.ecs.fetch::<Entity>();
         let mut gamelog = self.ecs.fetch_mut::<gamelog::GameLog>();
@@ -286,6 +358,8 @@ impl State {
     }
 
     fn game_over_cleanup(&mut self) {
+        let player_name = self.ecs.fetch::<OverallStats>().name.clone();
+
         // delete everything
         let mut to_delete = Vec::new();
         for e in self.ecs.entities().join() {
@@ -298,17 +372,56 @@ impl State {
         }
 
         self.reset_game();
+
+        // try again with the same adventurer
+        self.ecs.fetch_mut::<OverallStats>().set_name(&player_name);
+        self.apply_player_name();
+    }
+
+    fn sync_player_name_from_save(&mut self) {
+        let player_name;
+        {
+            let player_entity = self.ecs.fetch::<Entity>();
+            let names = self.ecs.read_storage::<Name>();
+            player_name = names
+                .get(*player_entity)
+                .map(|name| name.name.clone())
+                .unwrap_or_default();
+        }
+        self.ecs.fetch_mut::<OverallStats>().set_name(&player_name);
+    }
+
+    fn apply_player_name(&mut self) {
+        let player_name = self.ecs.fetch::<OverallStats>().name.clone();
+        {
+            let player_entity = self.ecs.fetch::<Entity>();
+            let mut names = self.ecs.write_storage::<Name>();
+            if let Some(name) = names.get_mut(*player_entity) {
+                name.name = player_name.clone();
+            }
+        }
+        self.ecs
+            .fetch_mut::<gamelog::GameLog>()
+            .log(LogEntry::Notification {
+                notification: format!("..the trees whisper a name: {}", player_name),
+            });
     }
 
     fn reset_game(&mut self) {
         let player_entity = spawners::player(&mut self.ecs, 0, 0);
         self.ecs.insert(player_entity);
-        self.ecs.insert(OverallStats::new());
+        let mut stats = OverallStats::new();
+        stats.run_seed = self
+            .ecs
+            .fetch_mut::<rltk::RandomNumberGenerator>()
+            .next_u64();
+        self.ecs.insert(stats);
         self.ecs.insert(LevelStats::new(1, 0, 30));
         self.ecs.insert(Map::new(1));
         self.ecs.insert(Point::new(0, 0));
-        self.ecs.insert(RunState::CoreLevelStart);
+        self.ecs.insert(RunState::OuterNameEntry);
         self.ecs.insert(particle_system::ParticleBuilder::new());
+        self.ecs.insert(day_night_system::WorldClock::new());
         self.ecs
             .insert(gamelog::GameLog::new(vec![LogEntry::Notification {
                 notification: "you begin to squint your eyes open, seeing a dark forest.."
@@ -332,6 +445,7 @@ impl State {
         }
 
         builder.spawn_entities(&mut self.ecs);
+        trade::restock_vendors(&mut self.ecs);
 
         // restart everything
         let mut player_position = self.ecs.write_resource::<Point>();
@@ -383,12 +497,10 @@ fn main() -> rltk::BError {
     gs.ecs.register::<Ranged>();
     gs.ecs.register::<InflictsDamage>();
     gs.ecs.register::<AreaOfEffect>();
-    gs.ecs.register::<Confusion>();
     gs.ecs.register::<SimpleMarker<SerializeMe>>();
     gs.ecs.register::<SerializationHelper>();
     gs.ecs.register::<Equippable>();
     gs.ecs.register::<Equipped>();
-    gs.ecs.register::<MeleePowerBonus>();
     gs.ecs.register::<DefenseBonus>();
     gs.ecs.register::<ParticleLifetime>();
     gs.ecs.register::<HungerClock>();
@@ -409,6 +521,47 @@ fn main() -> rltk::BError {
     gs.ecs.register::<Backpack>();
     gs.ecs.register::<GoodThyme>();
     gs.ecs.register::<CakeIngredient>();
+    gs.ecs.register::<Dialogue>();
+    gs.ecs.register::<Persona>();
+    gs.ecs.register::<Worth>();
+    gs.ecs.register::<Vendor>();
+    gs.ecs.register::<RangedAttack>();
+    gs.ecs.register::<WantsToShoot>();
+    gs.ecs.register::<ThirstClock>();
+    gs.ecs.register::<HoldsWater>();
+    gs.ecs.register::<PurifiesWater>();
+    gs.ecs.register::<Nocturnal>();
+    gs.ecs.register::<LightSource>();
+    gs.ecs.register::<Warm>();
+    gs.ecs.register::<Perishable>();
+    gs.ecs.register::<SlowsSpoilage>();
+    gs.ecs.register::<ForagingGear>();
+    gs.ecs.register::<Harvestable>();
+    gs.ecs.register::<HeardNoise>();
+    gs.ecs.register::<CutsTrees>();
+    gs.ecs.register::<WantsToClearPath>();
+    gs.ecs.register::<Ignites>();
+    gs.ecs.register::<KnownRecipes>();
+    gs.ecs.register::<Predator>();
+    gs.ecs.register::<Scavenger>();
+    gs.ecs.register::<Morale>();
+    gs.ecs.register::<Behaviour>();
+    gs.ecs.register::<AiMemory>();
+    gs.ecs.register::<NoiseModifier>();
+    gs.ecs.register::<Sneaking>();
+    gs.ecs.register::<Initiative>();
+    gs.ecs.register::<StatusEffects>();
+    gs.ecs.register::<InflictsStatus>();
+    gs.ecs.register::<MeleeWeapon>();
+    gs.ecs.register::<WantsToShove>();
+    gs.ecs.register::<Knockback>();
+    gs.ecs.register::<KnocksBack>();
+    gs.ecs.register::<WantsToThrow>();
+    gs.ecs.register::<Weight>();
+    gs.ecs.register::<Fragile>();
+    gs.ecs.register::<Bait>();
+    gs.ecs.register::<PackHunter>();
+    gs.ecs.register::<WizardBoss>();
     // new component register here
 
     gs.ecs.insert(SimpleMarkerAllocator::<SerializeMe>::new());
@@ -417,10 +570,28 @@ fn main() -> rltk::BError {
     gs.ecs.insert(rltk::RandomNumberGenerator::new());
     gs.ecs.insert(spawn_system::SpawnBuilder::new());
     gs.ecs.insert(rex_assets::RexAssets::new());
+    gs.ecs.insert(dialogue::DialogueLibrary::new());
+    gs.ecs.insert(converse::ConversationInput::new());
+    gs.ecs.insert(trade::BarterOffer::new());
+    gs.ecs.insert(crafting::RecipeBook::new());
+    gs.ecs.insert(crafting::CraftingSelection::new());
+    gs.ecs.insert(targeting::TargetingCursor::new());
+    gs.ecs.insert(noise_system::NoiseBuilder::new());
 
     // build the first level
     gs.reset_game();
 
+    // the web page asks for a name before the game loads, so skip asking twice
+    let web_player_name = window_fx::player_name();
+    if gs
+        .ecs
+        .fetch_mut::<OverallStats>()
+        .set_name(&web_player_name)
+    {
+        gs.apply_player_name();
+        gs.ecs.insert(RunState::CoreLevelStart);
+    }
+
     // start main loop
     rltk::main_loop(context, gs)
 }
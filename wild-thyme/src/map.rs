@@ -1,7 +1,9 @@
 use super::{Entity, Rect, World};
+use crate::day_night_system::{torchlight, WorldClock};
+use crate::weather::Weather;
 use rltk::{Algorithm2D, BaseMap, Point, Rltk, RGB};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub const MAPWIDTH: usize = 80;
 pub const MAPHEIGHT: usize = 43;
@@ -15,6 +17,8 @@ pub enum TileType {
     DownStairs,
     IngredientTable,
     JudgeCake,
+    Stump,
+    Ash,
 }
 
 #[derive(Default, Serialize, Deserialize, Clone)]
@@ -28,10 +32,27 @@ pub struct Map {
     pub blocked: Vec<bool>,
     pub depth: i32,
     pub bloodstains: HashSet<usize>,
+    #[serde(default)]
+    pub weather: Weather,
+    pub chop_progress: HashMap<usize, i32>,
+    // burning tiles and how many rounds they have left
+    pub fire: HashMap<usize, i32>,
+    // how many more tiles the current fire can catch before it runs out of steam
+    pub fire_fuel: i32,
 
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
     pub tile_content: Vec<Vec<Entity>>,
+
+    // rebuilt by the fog system every round
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    pub fog_tiles: HashSet<usize>,
+
+    // tiles lit by torches and fireflies, rebuilt with the player's view
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    pub lit_tiles: HashSet<usize>,
 }
 
 impl Map {
@@ -47,6 +68,12 @@ impl Map {
             tile_content: vec![Vec::new(); MAPCOUNT],
             depth: new_depth,
             bloodstains: HashSet::new(),
+            weather: Weather::Clear,
+            chop_progress: HashMap::new(),
+            fire: HashMap::new(),
+            fire_fuel: 0,
+            fog_tiles: HashSet::new(),
+            lit_tiles: HashSet::new(),
         }
     }
 
@@ -68,6 +95,14 @@ impl Map {
         }
     }
 
+    // true if nothing but open ground sits between the two points
+    pub fn has_line_of_fire(&self, from: Point, to: Point) -> bool {
+        rltk::line2d(rltk::LineAlg::Bresenham, from, to)
+            .iter()
+            .filter(|step| **step != from && **step != to)
+            .all(|step| self.tiles[self.xy_idx(step.x, step.y)] != TileType::Wall)
+    }
+
     pub fn clear_content_index(&mut self) {
         for content in self.tile_content.iter_mut() {
             content.clear();
@@ -77,6 +112,7 @@ impl Map {
 
 pub fn draw_map(ecs: &World, ctx: &mut Rltk) {
     let map = ecs.fetch::<Map>();
+    let clock = ecs.fetch::<WorldClock>();
 
     let mut y = 0;
     let mut x = 0;
@@ -117,12 +153,33 @@ pub fn draw_map(ecs: &World, ctx: &mut Rltk) {
                     fg = RGB::from_hex("#904070").expect("hardcoded");
                     glyph = rltk::to_cp437('░'); // wall_glyph(&*map, x, y);
                 }
+                TileType::Stump => {
+                    fg = RGB::from_hex("#6b4f2a").expect("hardcoded");
+                    glyph = rltk::to_cp437('ⁿ');
+                }
+                TileType::Ash => {
+                    fg = RGB::from_hex("#5a5450").expect("hardcoded");
+                    glyph = match prime_pattern_a(idx) {
+                        true => rltk::to_cp437(','),
+                        false => rltk::to_cp437('.'),
+                    };
+                }
             }
             if map.bloodstains.contains(&idx) {
                 fg = RGB::from_hex("#cc3f0c").expect("hardcoded");
             }
+            if let Some(rounds) = map.fire.get(&idx) {
+                fg = match rounds % 2 {
+                    0 => RGB::named(rltk::ORANGE),
+                    _ => RGB::from_hex("#e03010").expect("hardcoded"),
+                };
+            }
             if !map.visible_tiles[idx] {
                 fg = fg.to_greyscale()
+            } else if clock.is_dark() && map.lit_tiles.contains(&idx) {
+                fg = torchlight(fg);
+            } else {
+                fg = clock.tint(fg);
             }
             ctx.set(
                 x,
@@ -149,7 +206,7 @@ impl Algorithm2D for Map {
 
 impl BaseMap for Map {
     fn is_opaque(&self, idx: usize) -> bool {
-        self.tiles[idx as usize] == TileType::Wall
+        self.tiles[idx as usize] == TileType::Wall || self.fog_tiles.contains(&idx)
     }
 
     fn get_available_exits(&self, idx: usize) -> rltk::SmallVec<[(usize, f32); 10]> {
@@ -60,6 +60,23 @@ pub fn release_drunk(
     result_steps
 }
 
+// little springs seep out of the trees along the top edge of some rooms
+pub fn add_springs(map: &mut Map, rng: &mut RandomNumberGenerator, count: i32) {
+    for _i in 0..count {
+        let room = match rng.random_slice_entry(&map.rooms) {
+            Some(room) => *room,
+            None => return,
+        };
+        let (x, _) = room.center();
+        for spring_x in x - 1..=x + 1 {
+            let idx = map.xy_idx(spring_x, room.y1);
+            if room.y1 > 0 && map.tiles[idx] == TileType::Wall {
+                map.tiles[idx] = TileType::Water;
+            }
+        }
+    }
+}
+
 pub fn apply_room_to_map(map: &mut Map, room: &Rect) {
     apply_tile_to_map(map, room, TileType::Floor);
 }
@@ -5,10 +5,12 @@ use crate::{
     components::Position,
     map::{Map, TileType},
     map_builders::common::{
-        apply_horizontal_tunnel, apply_room_to_map, apply_vertical_tunnel, release_floor_drunk,
+        add_springs, apply_horizontal_tunnel, apply_room_to_map, apply_vertical_tunnel,
+        release_floor_drunk,
     },
     rect::Rect,
     spawners,
+    weather::roll_weather,
 };
 
 use super::MapBuilder;
@@ -100,6 +102,9 @@ impl DrunkardsWalkBuilder {
             }
         }
 
+        add_springs(&mut self.map, &mut rng, 2);
+        self.map.weather = roll_weather(self.depth, &mut rng);
+
         let stairs_room = self.map.rooms[self.map.rooms.len() - 1];
         let stairs_position = stairs_room.center();
         let stairs_idx = self.map.xy_idx(stairs_position.0, stairs_position.1);
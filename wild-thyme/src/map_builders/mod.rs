@@ -3,15 +3,25 @@ mod drunkards_walk;
 mod nest_level;
 mod simple_map;
 mod town_level;
+mod wizard_boss_level;
 mod wizard_level;
 
 use crate::{Map, Position, World};
 
 use self::{
     drunkards_walk::DrunkardsWalkBuilder, nest_level::NestLevelBuilder,
-    simple_map::SimpleMapBuilder, town_level::TownLevelBuilder, wizard_level::WizardLevelBuilder,
+    simple_map::SimpleMapBuilder, town_level::TownLevelBuilder,
+    wizard_boss_level::WizardBossLevelBuilder, wizard_level::WizardLevelBuilder,
 };
 
+pub const TOWN_DEPTH: i32 = 1;
+pub const WIZARD_BOSS_DEPTH: i32 = 11;
+
+// hand-built levels that fog, fire and axes should leave alone
+pub fn is_set_piece(depth: i32) -> bool {
+    depth == TOWN_DEPTH || depth == WIZARD_BOSS_DEPTH
+}
+
 pub trait MapBuilder {
     fn build_map(&mut self);
     fn spawn_entities(&mut self, ecs: &mut World);
@@ -22,8 +32,10 @@ pub trait MapBuilder {
 pub fn make_builder(new_depth: i32) -> Box<dyn MapBuilder> {
     // Box::new(TownLevelBuilder::new())
     // Box::new(WizardLevelBuilder::new())
-    if new_depth == 1 {
+    if new_depth == TOWN_DEPTH {
         Box::new(TownLevelBuilder::new())
+    } else if new_depth == WIZARD_BOSS_DEPTH {
+        Box::new(WizardBossLevelBuilder::new(new_depth))
     } else if new_depth % 3 == 0 {
         Box::new(NestLevelBuilder::new(new_depth))
     } else if new_depth % 5 == 0 {
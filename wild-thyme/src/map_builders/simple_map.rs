@@ -7,6 +7,7 @@ use crate::{
     map_builders::common::{apply_horizontal_tunnel, apply_room_to_map, apply_vertical_tunnel},
     rect::Rect,
     spawners,
+    weather::roll_weather,
 };
 
 use super::MapBuilder;
@@ -88,6 +89,8 @@ impl SimpleMapBuilder {
             }
         }
 
+        self.map.weather = roll_weather(self.depth, &mut rng);
+
         let stairs_position = self.map.rooms[self.map.rooms.len() - 1].center();
         let stairs_idx = self.map.xy_idx(stairs_position.0, stairs_position.1);
         self.map.tiles[stairs_idx] = TileType::DownStairs;
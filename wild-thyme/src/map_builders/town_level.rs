@@ -91,7 +91,7 @@ impl MapBuilder for TownLevelBuilder {
     }
 
     fn spawn_entities(&mut self, ecs: &mut World) {
-        spawners::npc(
+        let figure = spawners::npc(
             ecs,
             40,
             18,
@@ -100,6 +100,7 @@ impl MapBuilder for TownLevelBuilder {
             "MYSTERIOUS FIGURE",
             vec![
                 "been dark out here lately...".to_string(),
+                "evening, {NAME}...".to_string(),
                 "word is, there's good THYME deep in the forest".to_string(),
                 "I heard ancient lizards don't like ROCKS..".to_string(),
                 "FRIENDLY birds can help a person out..".to_string(),
@@ -111,8 +112,14 @@ impl MapBuilder for TownLevelBuilder {
                 "the GREAT BAKE OFF is to the SOUTH!...".to_string(),
             ],
         );
+        spawners::give_dialogue(ecs, figure, "MYSTERIOUS FIGURE");
+        spawners::give_persona(
+            ecs,
+            figure,
+            "a hooded stranger in the forest town who speaks in ominous half-hints about the forest, and is secretly a bake off judge",
+        );
         let cake_center = self.cake_room.center();
-        spawners::npc(
+        let hollywood = spawners::npc(
             ecs,
             cake_center.0 - 1,
             cake_center.1,
@@ -122,10 +129,17 @@ impl MapBuilder for TownLevelBuilder {
             vec![
                 "the GREAT WOODY BAKE OFF is coming soon!".to_string(),
                 "help us BAKE a CAKE".to_string(),
+                "ah, {NAME}! our newest baker".to_string(),
                 "my TASTE is absolutely exquisite".to_string(),
             ],
         );
-        spawners::npc(
+        spawners::give_dialogue(ecs, hollywood, "MR HOLLYWOOD");
+        spawners::give_persona(
+            ecs,
+            hollywood,
+            "a vain, stern celebrity judge of the GREAT WOODY BAKE OFF with absolutely exquisite taste",
+        );
+        let goodberry = spawners::npc(
             ecs,
             cake_center.0 + 1,
             cake_center.1,
@@ -135,9 +149,16 @@ impl MapBuilder for TownLevelBuilder {
             vec![
                 "I love CAKE of all shapes and sizes".to_string(),
                 "we will JUDGE your CAKE when the time comes!".to_string(),
+                "good luck out there, {NAME} dear".to_string(),
             ],
         );
-        spawners::npc(
+        spawners::give_dialogue(ecs, goodberry, "MS GOODBERRY");
+        spawners::give_persona(
+            ecs,
+            goodberry,
+            "a warm, grandmotherly bake off judge who loves cake of all shapes and sizes and calls everyone dear",
+        );
+        let fields = spawners::npc(
             ecs,
             cake_center.0,
             cake_center.1 + 1,
@@ -146,6 +167,7 @@ impl MapBuilder for TownLevelBuilder {
             "SIR FIELDS",
             vec![
                 "CAKE is made of several ingredients!".to_string(),
+                "listen well, {NAME}".to_string(),
                 "FLOUR will hold it together".to_string(),
                 "MILK will help it mix".to_string(),
                 "FAT will moisturize it".to_string(),
@@ -155,6 +177,11 @@ impl MapBuilder for TownLevelBuilder {
                 "THYME will bring it all together".to_string(),
             ],
         );
+        spawners::give_persona(
+            ecs,
+            fields,
+            "a pompous, knowledgeable bake off judge obsessed with the proper ingredients of a cake",
+        );
         ecs.create_entity()
             .with(Position { x: 38, y: 36 })
             .with(Name {
@@ -0,0 +1,137 @@
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+use crate::{
+    components::Position,
+    map::{Map, TileType},
+    map_builders::common::{apply_horizontal_tunnel, apply_room_to_map, release_floor_drunk},
+    random_table::RandomTable,
+    rect::Rect,
+    spawners::{self, spawn_specific_on_point},
+};
+
+use super::MapBuilder;
+
+const CLEARING: (i32, i32) = (56, 21);
+const CLEARING_RADIUS: f32 = 10.5;
+const MOAT_RADIUS: f32 = 7.0;
+
+// a winding path through the trees to the wizard's stump, ringed by water
+pub struct WizardBossLevelBuilder {
+    map: Map,
+    starting_position: Position,
+    start_room: Rect,
+    portal_room: Rect,
+    path_spawns: Vec<(i32, i32)>,
+}
+
+impl MapBuilder for WizardBossLevelBuilder {
+    fn build_map(&mut self) {
+        let mut rng = RandomNumberGenerator::new();
+
+        self.start_room = Rect::new(2, 17, 8, 8);
+        apply_room_to_map(&mut self.map, &self.start_room);
+        self.map.rooms.push(self.start_room);
+        let start = self.start_room.center();
+        self.starting_position = Position {
+            x: start.0,
+            y: start.1,
+        };
+
+        // the way in, roughed up so it doesn't look like anyone made it
+        apply_horizontal_tunnel(&mut self.map, start.0, CLEARING.0, CLEARING.1);
+        for x in (start.0 + 6..CLEARING.0 - 10).step_by(7) {
+            let steps = release_floor_drunk(&mut self.map, (x, CLEARING.1), 80, &mut rng, 20);
+            self.path_spawns.extend(steps);
+        }
+
+        // the clearing, with a moat you can only cross in a few places
+        let center = Point::new(CLEARING.0, CLEARING.1);
+        for y in 1..self.map.height - 1 {
+            for x in 1..self.map.width - 1 {
+                let distance = rltk::DistanceAlg::Pythagoras.distance2d(center, Point::new(x, y));
+                if distance > CLEARING_RADIUS {
+                    continue;
+                }
+                let ford = i32::abs(x - center.x) <= 1 || i32::abs(y - center.y) <= 1;
+                let idx = self.map.xy_idx(x, y);
+                self.map.tiles[idx] = match f32::abs(distance - MOAT_RADIUS) < 0.8 && !ford {
+                    true => TileType::Water,
+                    false => TileType::Floor,
+                };
+            }
+        }
+        for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let idx = self.map.xy_idx(center.x + dx, center.y + dy);
+            self.map.tiles[idx] = TileType::Stump;
+        }
+        self.map.rooms.push(Rect::new(
+            center.x - MOAT_RADIUS as i32,
+            center.y - MOAT_RADIUS as i32,
+            MOAT_RADIUS as i32 * 2,
+            MOAT_RADIUS as i32 * 2,
+        ));
+
+        // out the back, past the wizard
+        self.portal_room = Rect::new(70, 18, 6, 6);
+        apply_room_to_map(&mut self.map, &self.portal_room);
+        self.map.rooms.push(self.portal_room);
+        let portal = self.portal_room.center();
+        apply_horizontal_tunnel(&mut self.map, CLEARING.0, portal.0, portal.1);
+        let portal_idx = self.map.xy_idx(portal.0, portal.1);
+        self.map.tiles[portal_idx] = TileType::DownStairs;
+    }
+
+    fn spawn_entities(&mut self, ecs: &mut World) {
+        spawn_specific_on_point(ecs, CLEARING, &"THE WIZARD".to_string());
+        spawners::spawn_treeportal(ecs, &self.portal_room);
+
+        // enough lying around on the way in to strike a bargain
+        let spawn_table = RandomTable::new()
+            .add("MAGIC MUSHROOM", 6)
+            .add("GOOD THYME", 2)
+            .add("MUSHROOM RING", 3)
+            .add("BERRY BUSH", 4)
+            .add("HEALING HERBS", 4)
+            .add("ROCK", 4)
+            .add("DEER", 2)
+            .add("FROG", 3);
+        // the clearing may have flooded some of the path, so only use what's still dry
+        let spots: Vec<(i32, i32)> = self
+            .path_spawns
+            .iter()
+            .filter(|p| self.map.tiles[self.map.xy_idx(p.0, p.1)] == TileType::Floor)
+            .copied()
+            .collect();
+        let mut spawns = vec!["MAGIC MUSHROOM".to_string(), "GOOD THYME".to_string()];
+        {
+            let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+            while spawns.len() < spots.len() {
+                spawns.push(spawn_table.roll(&mut rng));
+            }
+        }
+        for (point, spawn) in spots.iter().zip(spawns.iter()) {
+            spawn_specific_on_point(ecs, *point, spawn);
+        }
+    }
+
+    fn get_map(&mut self) -> Map {
+        self.map.clone()
+    }
+
+    fn get_starting_position(&mut self) -> Position {
+        self.starting_position.clone()
+    }
+}
+
+impl WizardBossLevelBuilder {
+    pub fn new(depth: i32) -> WizardBossLevelBuilder {
+        WizardBossLevelBuilder {
+            map: Map::new(depth),
+            starting_position: Position { x: 0, y: 0 },
+            start_room: Rect::new(0, 0, 0, 0),
+            portal_room: Rect::new(0, 0, 0, 0),
+            path_spawns: Vec::new(),
+        }
+    }
+}
@@ -59,7 +59,7 @@ impl MapBuilder for WizardLevelBuilder {
     }
 
     fn spawn_entities(&mut self, ecs: &mut World) {
-        spawners::npc(
+        let druid = spawners::npc(
             ecs,
             40,
             18,
@@ -71,6 +71,8 @@ impl MapBuilder for WizardLevelBuilder {
                 "a FRIENDLY EAGLE will help you get home".to_string(),
                 "make sure you have the ingredients you need!".to_string(),
                 "hello, friend".to_string(),
+                "{NAME}! I'd know that face anywhere".to_string(),
+                "I've been watching you, {NAME}. from a distance. mostly".to_string(),
                 "I sure love to cook".to_string(),
                 "the old ways teach us to GIVE".to_string(),
                 "there is nothing but PAIN beyond this level".to_string(),
@@ -78,6 +80,12 @@ impl MapBuilder for WizardLevelBuilder {
                 "only a sense of PRIDE and SATISFACTION beyond here".to_string(),
             ],
         );
+        spawners::give_vendor(ecs, druid, "PASTRIES");
+        spawners::give_persona(
+            ecs,
+            druid,
+            "a friendly, uncomfortably weird wizard baker living in a foggy tree stump, who forages strange oddities for psychedelic pastries, isn't sure if he's really a wizard, and thinks he knows everyone",
+        );
         spawners::spawn_treeportal(ecs, &self.portal_room);
 
         let spawn_table = RandomTable::new()
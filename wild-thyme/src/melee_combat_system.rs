@@ -1,12 +1,46 @@
 use crate::{
-    components::{DefenseBonus, Equipped, HungerClock, HungerState, MeleePowerBonus, Position},
+    components::{
+        DefenseBonus, Equipped, HungerClock, HungerState, InflictsStatus, Initiative, Knockback,
+        KnocksBack, MeleeWeapon, Position, StatusEffects, StatusKind, WeaponTrait,
+    },
     gamelog::LogEntry,
+    initiative_system::{ACTION_COST, NORMAL_SPEED},
+    noise_system::{NoiseBuilder, FIGHT_NOISE},
     particle_system::ParticleBuilder,
 };
 
 use super::{gamelog::GameLog, CombatStats, Name, SufferDamage, WantsToMelee};
+use rltk::{Point, RandomNumberGenerator};
 use specs::prelude::*;
 
+// a d20 plus the attacker's bonuses has to reach this plus the target's dodge
+const DODGE_BASE: i32 = 8;
+const CRITICAL_ROLL: i32 = 20;
+
+// quick little things are harder to hit, and the dazed and drowsy are sitting ducks
+fn dodge(
+    stats: &CombatStats,
+    initiative: Option<&Initiative>,
+    status: Option<&StatusEffects>,
+) -> i32 {
+    let mut dodge = stats.dodge;
+    if let Some(initiative) = initiative {
+        dodge += (initiative.speed - NORMAL_SPEED) / 25;
+    }
+    if let Some(status) = status {
+        if status.has(StatusKind::Hasted) {
+            dodge += 2;
+        }
+        if status.has(StatusKind::Slowed) {
+            dodge -= 2;
+        }
+        if status.has(StatusKind::Confused) || status.has(StatusKind::Sleepy) {
+            dodge = 0;
+        }
+    }
+    i32::max(0, dodge)
+}
+
 pub struct MeleeCombatSystem {}
 
 impl<'a> System<'a> for MeleeCombatSystem {
@@ -17,12 +51,19 @@ impl<'a> System<'a> for MeleeCombatSystem {
         ReadStorage<'a, Name>,
         ReadStorage<'a, CombatStats>,
         WriteStorage<'a, SufferDamage>,
-        ReadStorage<'a, MeleePowerBonus>,
+        ReadStorage<'a, MeleeWeapon>,
         ReadStorage<'a, DefenseBonus>,
         ReadStorage<'a, Equipped>,
         WriteExpect<'a, ParticleBuilder>,
         ReadStorage<'a, Position>,
         ReadStorage<'a, HungerClock>,
+        WriteExpect<'a, NoiseBuilder>,
+        ReadStorage<'a, InflictsStatus>,
+        WriteStorage<'a, StatusEffects>,
+        WriteStorage<'a, Initiative>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        ReadStorage<'a, KnocksBack>,
+        WriteStorage<'a, Knockback>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -33,30 +74,44 @@ impl<'a> System<'a> for MeleeCombatSystem {
             names,
             combat_stats,
             mut inflict_damage,
-            melee_power_bonuses,
+            melee_weapons,
             defense_bonuses,
             equipped,
             mut particle_builder,
             positions,
             hunger_clock,
+            mut noise_builder,
+            inflicts_status,
+            mut statuses,
+            mut initiatives,
+            mut rng,
+            knocks_back,
+            mut knockbacks,
         ) = data;
 
         for (entity, wants_melee, name, stats) in
             (&entities, &wants_melee, &names, &combat_stats).join()
         {
             if stats.hp > 0 {
-                let mut offensive_bonus = 0;
-                for (_item_entity, power_bonus, equipped_by) in
-                    (&entities, &melee_power_bonuses, &equipped).join()
-                {
-                    if equipped_by.owner == entity {
-                        offensive_bonus += power_bonus.power;
-                    }
+                let weapon = (&entities, &melee_weapons, &equipped)
+                    .join()
+                    .find(|(_, _, equipped_by)| equipped_by.owner == entity)
+                    .map(|(item, weapon, _)| (item, weapon));
+                let mut hit_bonus = stats.power / 2;
+                let mut damage_bonus = 0;
+                if let Some((_, weapon)) = weapon {
+                    hit_bonus += weapon.hit_bonus;
+                    damage_bonus += weapon.bonus;
                 }
                 let hc = hunger_clock.get(entity);
                 if let Some(hc) = hc {
                     if hc.state == HungerState::Full {
-                        offensive_bonus += 1;
+                        damage_bonus += 1;
+                    }
+                }
+                if let Some(status) = statuses.get(entity) {
+                    if status.has(StatusKind::High) {
+                        hit_bonus -= 2;
                     }
                 }
 
@@ -68,16 +123,65 @@ impl<'a> System<'a> for MeleeCombatSystem {
                         .get(wants_melee.target)
                         .expect("WantsToMelee should only exist on entities that also have Name");
 
-                    let mut defensive_bonus = 0;
+                    let mut armor = target_stats.defense;
                     for (_item_entity, defense_bonus, equipped_by) in
                         (&entities, &defense_bonuses, &equipped).join()
                     {
                         if equipped_by.owner == wants_melee.target {
-                            defensive_bonus += defense_bonus.defense;
+                            armor += defense_bonus.defense;
                         }
                     }
 
+                    // how it happened, for the log
+                    let mut how = Vec::new();
+                    if let Some((item, _)) = weapon {
+                        if let Some(weapon_name) = names.get(item) {
+                            how.push(format!("with the {}", weapon_name.name));
+                        }
+                    }
+                    if let (Some(from), Some(to)) =
+                        (positions.get(entity), positions.get(wants_melee.target))
+                    {
+                        if rltk::DistanceAlg::Pythagoras
+                            .distance2d(Point::new(from.x, from.y), Point::new(to.x, to.y))
+                            > 1.5
+                        {
+                            how.push(format!("from a distance"));
+                        }
+                    }
+                    let told = |rest: String| {
+                        how.iter()
+                            .cloned()
+                            .chain(std::iter::once(rest))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    };
+
                     let pos = positions.get(wants_melee.target);
+                    if let Some(pos) = pos {
+                        noise_builder.request(pos.x, pos.y, FIGHT_NOISE);
+                    }
+
+                    let target_dodge = dodge(
+                        target_stats,
+                        initiatives.get(wants_melee.target),
+                        statuses.get(wants_melee.target),
+                    );
+                    let roll = rng.roll_dice(1, 20);
+                    let critical = roll == CRITICAL_ROLL;
+                    if !critical && (roll == 1 || roll + hit_bonus < DODGE_BASE + target_dodge) {
+                        log.log(LogEntry::Action {
+                            subject: format!("{}", &name.name),
+                            verb: format!("miss"),
+                            object: format!("{}", &target_name.name),
+                            suffix: match target_dodge {
+                                0 => told(format!("swinging wide.")),
+                                _ => told(format!("as it dodges out of the way.")),
+                            },
+                        });
+                        continue;
+                    }
+
                     if let Some(pos) = pos {
                         particle_builder.request(
                             pos.x,
@@ -89,25 +193,92 @@ impl<'a> System<'a> for MeleeCombatSystem {
                         );
                     }
 
-                    let damage = i32::max(
-                        0,
-                        (stats.power + offensive_bonus) - (target_stats.defense + defensive_bonus),
-                    );
+                    // a critical rolls all the dice twice
+                    let mut damage = stats.power / 2 + damage_bonus;
+                    for _ in 0..(if critical { 2 } else { 1 }) {
+                        damage += rng.roll_dice(1, i32::max(1, stats.power));
+                        if let Some((_, weapon)) = weapon {
+                            damage += rng.roll_dice(weapon.n_dice, weapon.die_type);
+                        }
+                    }
+                    // armor takes the edge off, but not reliably
+                    let absorbed = match armor > 0 {
+                        true => i32::min(damage, rng.roll_dice(1, armor + 1) - 1),
+                        false => 0,
+                    };
+                    let damage = damage - absorbed;
+
                     if damage == 0 {
                         log.log(LogEntry::Action {
                             subject: format!("{}", &name.name),
-                            verb: format!("is not able to hurt"),
+                            verb: format!("hit"),
                             object: format!("{}", &target_name.name),
-                            suffix: format!("."),
+                            suffix: told(format!("but it glances off harmlessly.")),
                         });
                     } else {
                         log.log(LogEntry::Action {
                             subject: format!("{}", &name.name),
-                            verb: format!("hit"),
+                            verb: match critical {
+                                true => format!("critically hit"),
+                                false => format!("hit"),
+                            },
                             object: format!("{}", &target_name.name),
-                            suffix: format!("for {} HP!", damage),
+                            suffix: match absorbed {
+                                0 => told(format!("for {} HP!", damage)),
+                                _ => told(format!("for {} HP! ({} soaked up)", damage, absorbed)),
+                            },
                         });
                         SufferDamage::new_damage(&mut inflict_damage, wants_melee.target, damage);
+                        // venomous bites and the like
+                        if let Some(inflicts) = inflicts_status.get(entity) {
+                            StatusEffects::inflict(
+                                &mut statuses,
+                                wants_melee.target,
+                                inflicts.kind,
+                                inflicts.turns,
+                            );
+                            log.log(LogEntry::Action {
+                                subject: format!("{}", &target_name.name),
+                                verb: format!("is"),
+                                object: format!("{}", inflicts.kind.name()),
+                                suffix: format!("!"),
+                            });
+                        }
+                        // heavy weapons can knock the target off its stride
+                        let staggers = weapon.map_or(false, |(_, weapon)| {
+                            weapon.traits.contains(&WeaponTrait::Stagger)
+                        });
+                        if staggers && (critical || rng.roll_dice(1, 2) == 1) {
+                            if let Some(initiative) = initiatives.get_mut(wants_melee.target) {
+                                initiative.energy -= ACTION_COST;
+                                log.log(LogEntry::Notification {
+                                    notification: format!("the {} staggers!", target_name.name),
+                                });
+                            }
+                        }
+                        // and some hit hard enough to send it flying
+                        let force = knocks_back.get(entity).map_or(0, |k| k.force)
+                            + weapon.map_or(0, |(_, weapon)| {
+                                weapon.traits.contains(&WeaponTrait::Knockback) as i32
+                            });
+                        if let (true, Some(from), Some(to)) =
+                            (force > 0, positions.get(entity), pos)
+                        {
+                            let _ = knockbacks.insert(
+                                wants_melee.target,
+                                Knockback {
+                                    dx: i32::signum(to.x - from.x),
+                                    dy: i32::signum(to.y - from.y),
+                                    force,
+                                },
+                            );
+                            log.log(LogEntry::Action {
+                                subject: format!("{}", &target_name.name),
+                                verb: format!("is"),
+                                object: format!("knocked back"),
+                                suffix: format!("!"),
+                            });
+                        }
                     }
                 }
             }
@@ -1,8 +1,9 @@
 use rltk::{Rltk, VirtualKeyCode, RGB};
 
 use crate::{
-    gui::{MainMenuResult, MainMenuSelection},
+    gui::{MainMenuResult, MainMenuSelection, NameEntryResult},
     rex_assets::RexAssets,
+    stats::{validate_player_name, OverallStats, MAX_PLAYER_NAME_LENGTH},
     RunState, State,
 };
 
@@ -148,3 +149,109 @@ pub fn main_menu(gs: &mut State, ctx: &mut Rltk) -> MainMenuResult {
         selected: crate::gui::MainMenuSelection::NewGame,
     }
 }
+
+pub fn name_entry(gs: &mut State, ctx: &mut Rltk) -> NameEntryResult {
+    let assets = gs.ecs.fetch::<RexAssets>();
+    let mut stats = gs.ecs.fetch_mut::<OverallStats>();
+    ctx.render_xp_sprite(&assets.menu, 0, 0);
+
+    ctx.draw_box(
+        20,
+        14,
+        39,
+        2,
+        RGB::from_hex("#808030").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color_centered(
+        15,
+        RGB::named(rltk::BURLYWOOD),
+        RGB::named(rltk::BLACK),
+        "And We Had a Wild Thyme",
+    );
+
+    ctx.draw_box(
+        25,
+        21,
+        29,
+        7,
+        RGB::from_hex("#808030").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color_centered(
+        23,
+        RGB::named(rltk::WHITE),
+        RGB::named(rltk::BLACK),
+        "who are you?",
+    );
+    let name_color = match validate_player_name(&stats.name) {
+        Some(_) => RGB::from_hex("#70e0a0").expect("hardcoded"),
+        None => RGB::from_hex("#e08080").expect("hardcoded"),
+    };
+    ctx.print_color_centered(
+        25,
+        name_color,
+        RGB::named(rltk::BLACK),
+        format!("{}_", stats.name),
+    );
+    ctx.print_color_centered(
+        27,
+        RGB::from_hex("#808030").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+        "[ENTER] to begin",
+    );
+    ctx.print_color_centered(
+        30,
+        RGB::from_hex("#606060").expect("hardcoded"),
+        RGB::named(rltk::BLACK),
+        format!("up to {} letters or numbers", MAX_PLAYER_NAME_LENGTH),
+    );
+
+    match ctx.key {
+        None => NameEntryResult::NoSelection,
+        Some(key) => match key {
+            VirtualKeyCode::Back => {
+                stats.name.pop();
+                NameEntryResult::NoSelection
+            }
+            VirtualKeyCode::Return => {
+                let entered_name = stats.name.clone();
+                if stats.set_name(&entered_name) {
+                    NameEntryResult::Selected
+                } else {
+                    NameEntryResult::NoSelection
+                }
+            }
+            _ => {
+                if let Some(c) = key_to_name_char(key) {
+                    if stats.name.chars().count() < MAX_PLAYER_NAME_LENGTH {
+                        stats.name.push(c);
+                    }
+                }
+                NameEntryResult::NoSelection
+            }
+        },
+    }
+}
+
+pub fn key_to_name_char(key: VirtualKeyCode) -> Option<char> {
+    let letter = rltk::letter_to_option(key);
+    if letter > -1 {
+        return Some((b'A' + letter as u8) as char);
+    }
+    match key {
+        VirtualKeyCode::Key0 | VirtualKeyCode::Numpad0 => Some('0'),
+        VirtualKeyCode::Key1 | VirtualKeyCode::Numpad1 => Some('1'),
+        VirtualKeyCode::Key2 | VirtualKeyCode::Numpad2 => Some('2'),
+        VirtualKeyCode::Key3 | VirtualKeyCode::Numpad3 => Some('3'),
+        VirtualKeyCode::Key4 | VirtualKeyCode::Numpad4 => Some('4'),
+        VirtualKeyCode::Key5 | VirtualKeyCode::Numpad5 => Some('5'),
+        VirtualKeyCode::Key6 | VirtualKeyCode::Numpad6 => Some('6'),
+        VirtualKeyCode::Key7 | VirtualKeyCode::Numpad7 => Some('7'),
+        VirtualKeyCode::Key8 | VirtualKeyCode::Numpad8 => Some('8'),
+        VirtualKeyCode::Key9 | VirtualKeyCode::Numpad9 => Some('9'),
+        VirtualKeyCode::Space => Some(' '),
+        VirtualKeyCode::Minus | VirtualKeyCode::Underline => Some('_'),
+        _ => None,
+    }
+}
@@ -1,12 +1,24 @@
 use crate::{
-    components::{Confusion, EntityMoved, HostileToPlayer, WantsToSwap},
+    components::{
+        AiMemory, AiState, Behaviour, EntityMoved, Goal, HeardNoise, Herbivore, HostileToPlayer,
+        Initiative, Name, PackHunter, RangedAttack, StatusEffects, StatusKind, WantsToShoot,
+        WantsToSwap,
+    },
+    gamelog::{GameLog, LogEntry},
+    initiative_system::ACTION_COST,
     particle_system::ParticleBuilder,
+    stats::LevelStats,
 };
 
 use super::{Map, Monster, Position, RunState, Viewshed, WantsToMelee};
 use rltk::{Point, RandomNumberGenerator};
 use specs::prelude::*;
 
+const FORGET_TURNS: i32 = 12;
+const WANDER_RADIUS: f32 = 6.0;
+const SHOUT_RADIUS: f32 = 8.0;
+const FLANK_SPREAD: f32 = 4.0;
+
 pub struct MonsterAI {}
 
 impl<'a> System<'a> for MonsterAI {
@@ -21,11 +33,22 @@ impl<'a> System<'a> for MonsterAI {
         WriteStorage<'a, Position>,
         WriteStorage<'a, WantsToMelee>,
         WriteStorage<'a, WantsToSwap>,
-        WriteStorage<'a, Confusion>,
+        ReadStorage<'a, StatusEffects>,
         WriteExpect<'a, ParticleBuilder>,
         WriteStorage<'a, EntityMoved>,
         ReadStorage<'a, HostileToPlayer>,
         WriteExpect<'a, RandomNumberGenerator>,
+        WriteStorage<'a, RangedAttack>,
+        WriteStorage<'a, WantsToShoot>,
+        WriteStorage<'a, HeardNoise>,
+        ReadStorage<'a, Behaviour>,
+        ReadStorage<'a, Herbivore>,
+        WriteStorage<'a, AiMemory>,
+        ReadStorage<'a, Name>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+        WriteStorage<'a, Initiative>,
+        ReadStorage<'a, PackHunter>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -40,95 +63,304 @@ impl<'a> System<'a> for MonsterAI {
             mut positions,
             mut wants_to_melee,
             mut wants_to_swap,
-            mut confused,
+            statuses,
             mut particle_builder,
             mut entity_moved,
             hostile,
             mut rng,
+            mut ranged_attacks,
+            mut wants_to_shoot,
+            mut heard_noise,
+            behaviours,
+            herbivores,
+            mut memories,
+            names,
+            mut log,
+            mut level_stats,
+            mut initiatives,
+            pack_hunters,
         ) = data;
 
-        if *runstate != RunState::CoreMonsterTurn {
+        if !matches!(
+            *runstate,
+            RunState::CoreMonsterTurn | RunState::CoreMonsterExtraTurn
+        ) {
             return;
         }
 
+        let mut shouts: Vec<(Entity, Point)> = Vec::new();
+        let pack: Vec<(Entity, Point)> = (&entities, &pack_hunters, &positions)
+            .join()
+            .map(|(e, _, pos)| (e, Point::new(pos.x, pos.y)))
+            .collect();
+
         for (entity, mut viewshed, _monster, mut pos) in
             (&entities, &mut viewshed, &monster, &mut positions).join()
         {
+            // slow mobs wait until they've built up enough energy, fast ones go more than once
+            match initiatives.get_mut(entity) {
+                Some(initiative) if initiative.energy >= ACTION_COST => {
+                    initiative.energy -= ACTION_COST;
+                }
+                Some(_) => continue,
+                None if *runstate != RunState::CoreMonsterTurn => continue,
+                None => {}
+            }
+
             let mut can_act = true;
 
-            let is_confused = confused.get_mut(entity);
-            if let Some(i_am_confused) = is_confused {
-                i_am_confused.turns -= 1;
-                if i_am_confused.turns < 1 {
-                    confused.remove(entity);
+            if let Some(ranged) = ranged_attacks.get_mut(entity) {
+                if ranged.cooldown_remaining > 0 {
+                    ranged.cooldown_remaining -= 1;
                 }
-                can_act = false;
+            }
 
-                particle_builder.request(
-                    pos.x,
-                    pos.y,
-                    rltk::RGB::named(rltk::PURPLE),
-                    rltk::RGB::named(rltk::BLACK),
-                    rltk::to_cp437('?'),
-                    200.0,
-                );
+            if let Some(status) = statuses.get(entity) {
+                if status.has(StatusKind::Confused) {
+                    can_act = false;
+                    particle_builder.request(
+                        pos.x,
+                        pos.y,
+                        rltk::RGB::named(rltk::PURPLE),
+                        rltk::RGB::named(rltk::BLACK),
+                        rltk::to_cp437('?'),
+                        200.0,
+                    );
+                } else if status.has(StatusKind::Sleepy) && rng.roll_dice(1, 3) == 1 {
+                    // nodded off for a moment
+                    can_act = false;
+                    particle_builder.request(
+                        pos.x,
+                        pos.y,
+                        rltk::RGB::named(rltk::SLATE_BLUE),
+                        rltk::RGB::named(rltk::BLACK),
+                        rltk::to_cp437('z'),
+                        200.0,
+                    );
+                } else if status.has(StatusKind::High) && rng.roll_dice(1, 2) == 1 {
+                    // too busy looking at the pretty colours
+                    can_act = false;
+                    if wander(&mut map, &mut pos, &mut rng, None) {
+                        viewshed.dirty = true;
+                        entity_moved
+                            .insert(entity, EntityMoved {})
+                            .expect("should be able to add movement marker");
+                    }
+                }
             }
 
             if !can_act {
                 continue;
             }
 
+            // fleeing, hunting, scavenging and herding come before everything else
+            if let Some(behaviour) = behaviours.get(entity) {
+                let moved = match behaviour.goal {
+                    Goal::Flee { x, y } => step_away(&mut map, &mut pos, Point::new(x, y)),
+                    Goal::Hunt { x, y } => {
+                        let adjacent = rltk::DistanceAlg::Pythagoras
+                            .distance2d(Point::new(pos.x, pos.y), Point::new(x, y))
+                            < 1.5;
+                        if adjacent {
+                            let idx = map.xy_idx(x, y);
+                            if let Some(prey) = map.tile_content[idx]
+                                .iter()
+                                .find(|e| herbivores.get(**e).is_some())
+                            {
+                                wants_to_melee
+                                    .insert(entity, WantsToMelee { target: *prey })
+                                    .expect("unable to insert attack");
+                            }
+                            false
+                        } else {
+                            step_toward(&mut map, &mut pos, Point::new(x, y))
+                        }
+                    }
+                    Goal::Scavenge { x, y } | Goal::Herd { x, y } => {
+                        step_toward(&mut map, &mut pos, Point::new(x, y))
+                    }
+                };
+                if matches!(behaviour.goal, Goal::Flee { .. }) {
+                    if let Some(memory) = memories.get_mut(entity) {
+                        memory.state = AiState::Fleeing;
+                    }
+                }
+                if moved {
+                    viewshed.dirty = true;
+                    entity_moved
+                        .insert(entity, EntityMoved {})
+                        .expect("should be able to add movement marker");
+                }
+                continue;
+            }
+
             let distance =
                 rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
-            if let Some(_) = hostile.get(entity) {
-                // if hostile, move close and attack
-                if distance < 1.5 {
-                    wants_to_melee
+            if let Some(ranged) = ranged_attacks.get_mut(entity) {
+                // hostiles would rather bite when they're close enough
+                let prefers_melee = hostile.get(entity).is_some() && distance < 1.5;
+                if ranged.cooldown_remaining == 0
+                    && !prefers_melee
+                    && distance <= ranged.range as f32
+                    && viewshed.visible_tiles.contains(&*player_pos)
+                    && map.has_line_of_fire(Point::new(pos.x, pos.y), *player_pos)
+                {
+                    wants_to_shoot
                         .insert(
                             entity,
-                            WantsToMelee {
+                            WantsToShoot {
                                 target: *player_entity,
                             },
                         )
-                        .expect("unable to insert attack");
-                } else if viewshed.visible_tiles.contains(&*player_pos) {
-                    let path = rltk::a_star_search(
-                        map.xy_idx(pos.x, pos.y) as i32,
-                        map.xy_idx(player_pos.x, player_pos.y) as i32,
-                        &mut *map,
-                    );
-                    if path.success && path.steps.len() > 1 {
-                        let mut idx = map.xy_idx(pos.x, pos.y);
-                        map.blocked[idx] = false;
-                        pos.x = path.steps[1] as i32 % map.width;
-                        pos.y = path.steps[1] as i32 / map.width;
-                        idx = map.xy_idx(pos.x, pos.y);
-                        map.blocked[idx] = true;
-                        viewshed.dirty = true;
-                        entity_moved
-                            .insert(entity, EntityMoved {})
-                            .expect("should be able to add movement marker");
+                        .expect("unable to insert ranged attack");
+                    ranged.cooldown_remaining = ranged.cooldown;
+                    continue;
+                }
+            }
+            if hostile.get(entity).is_some() {
+                if !memories.contains(entity) {
+                    let _ = memories.insert(entity, AiMemory::new(pos.x, pos.y));
+                }
+                let memory = memories.get_mut(entity).expect("just inserted");
+                let here = Point::new(pos.x, pos.y);
+                let sees_player = viewshed.visible_tiles.contains(&*player_pos);
+
+                if sees_player {
+                    heard_noise.remove(entity);
+                    memory.last_seen = Some((player_pos.x, player_pos.y));
+                    memory.forget_timer = FORGET_TURNS;
+                    memory.chasing_player = true;
+                    if !matches!(memory.state, AiState::Alerted | AiState::Chasing) {
+                        // spotted! stop for a moment to raise the alarm
+                        memory.state = AiState::Alerted;
+                        shouts.push((entity, here));
+                        particle_builder.request(
+                            pos.x,
+                            pos.y,
+                            rltk::RGB::named(rltk::YELLOW),
+                            rltk::RGB::named(rltk::BLACK),
+                            rltk::to_cp437('!'),
+                            300.0,
+                        );
+                        // unless the player is close enough to bite right away
+                        if distance >= 1.5 {
+                            continue;
+                        }
+                    } else {
+                        memory.state = AiState::Chasing;
+                    }
+                } else if let Some(noise) = heard_noise.get(entity) {
+                    // go and see what all the racket was about
+                    if memory.state != AiState::Chasing {
+                        memory.state = AiState::Searching;
+                        memory.last_seen = Some((noise.x, noise.y));
+                        memory.forget_timer = FORGET_TURNS / 2;
+                    }
+                    heard_noise.remove(entity);
+                }
+
+                let home = Point::new(memory.home.0, memory.home.1);
+                let last_seen = memory.last_seen.map(|(x, y)| Point::new(x, y));
+                let moved = match memory.state {
+                    AiState::Idle => {
+                        if rng.roll_dice(1, 6) == 1 {
+                            memory.state = AiState::Wandering;
+                        }
+                        false
+                    }
+                    AiState::Wandering => {
+                        if rng.roll_dice(1, 8) == 1 {
+                            memory.state = AiState::Idle;
+                        }
+                        wander(&mut map, &mut pos, &mut rng, Some((home, WANDER_RADIUS)))
+                    }
+                    AiState::Alerted | AiState::Chasing => {
+                        if !sees_player {
+                            memory.state = AiState::Searching;
+                        }
+                        if sees_player && distance < 1.5 {
+                            wants_to_melee
+                                .insert(
+                                    entity,
+                                    WantsToMelee {
+                                        target: *player_entity,
+                                    },
+                                )
+                                .expect("unable to insert attack");
+                            false
+                        } else if sees_player && pack_hunters.contains(entity) {
+                            let flank = flank_tile(&map, here, *player_pos, &pack, entity);
+                            step_toward(&mut map, &mut pos, flank)
+                        } else {
+                            match last_seen {
+                                Some(target) => step_toward(&mut map, &mut pos, target),
+                                None => false,
+                            }
+                        }
+                    }
+                    AiState::Searching => {
+                        memory.forget_timer -= 1;
+                        match last_seen {
+                            Some(target) if memory.forget_timer > 0 => {
+                                if rltk::DistanceAlg::Pythagoras.distance2d(here, target) < 1.5 {
+                                    // nobody here, have a sniff around
+                                    wander(&mut map, &mut pos, &mut rng, None)
+                                } else {
+                                    step_toward(&mut map, &mut pos, target)
+                                }
+                            }
+                            _ => {
+                                // gave up, head back home
+                                if memory.chasing_player {
+                                    level_stats.chases_escaped += 1;
+                                    if map.visible_tiles[map.xy_idx(pos.x, pos.y)] {
+                                        if let Some(name) = names.get(entity) {
+                                            log.log(LogEntry::Notification {
+                                                notification: format!(
+                                                    "the {} gives up the chase.",
+                                                    name.name
+                                                ),
+                                            });
+                                        }
+                                    }
+                                }
+                                memory.state = AiState::Returning;
+                                memory.last_seen = None;
+                                memory.chasing_player = false;
+                                false
+                            }
+                        }
+                    }
+                    AiState::Returning => {
+                        if rltk::DistanceAlg::Pythagoras.distance2d(here, home) < 1.5 {
+                            memory.state = AiState::Idle;
+                            false
+                        } else {
+                            step_toward(&mut map, &mut pos, home)
+                        }
+                    }
+                    AiState::Fleeing => {
+                        // calmed down, go back to where the player was last seen
+                        memory.state = match last_seen {
+                            Some(_) => AiState::Searching,
+                            None => AiState::Returning,
+                        };
+                        false
                     }
+                };
+                if moved {
+                    viewshed.dirty = true;
+                    entity_moved
+                        .insert(entity, EntityMoved {})
+                        .expect("should be able to add movement marker");
                 }
             } else {
                 // if not hostile, just wander
-                if distance > 2. {
-                    let new_pos = (
-                        pos.x + rng.roll_dice(1, 3) - 2,
-                        pos.y + rng.roll_dice(1, 3) - 2,
-                    );
-                    let new_pos_idx = map.xy_idx(new_pos.0, new_pos.1);
-                    if !map.blocked[new_pos_idx] {
-                        let old_pos_idx = map.xy_idx(pos.x, pos.y);
-                        map.blocked[old_pos_idx] = false;
-                        pos.x = new_pos.0;
-                        pos.y = new_pos.1;
-                        map.blocked[new_pos_idx] = true;
-                        viewshed.dirty = true;
-                        entity_moved
-                            .insert(entity, EntityMoved {})
-                            .expect("should be able to add movement marker");
-                    }
+                if distance > 2. && wander(&mut map, &mut pos, &mut rng, None) {
+                    viewshed.dirty = true;
+                    entity_moved
+                        .insert(entity, EntityMoved {})
+                        .expect("should be able to add movement marker");
                 }
             }
         }
@@ -155,5 +387,203 @@ impl<'a> System<'a> for MonsterAI {
             }
         }
         wants_to_swap.clear();
+
+        // a shout brings nearby friends looking for the player
+        for (shouter, shout_pos) in shouts.iter() {
+            let mut alerted = 0;
+            for (ally, memory, ally_pos, _hostile) in
+                (&entities, &mut memories, &positions, &hostile).join()
+            {
+                if ally == *shouter
+                    || matches!(memory.state, AiState::Alerted | AiState::Chasing)
+                    || rltk::DistanceAlg::Pythagoras
+                        .distance2d(*shout_pos, Point::new(ally_pos.x, ally_pos.y))
+                        > SHOUT_RADIUS
+                {
+                    continue;
+                }
+                memory.state = AiState::Searching;
+                memory.last_seen = Some((player_pos.x, player_pos.y));
+                memory.forget_timer = FORGET_TURNS;
+                memory.chasing_player = true;
+                alerted += 1;
+            }
+            let idx = map.xy_idx(shout_pos.x, shout_pos.y);
+            if alerted > 0 && map.visible_tiles[idx] {
+                if let Some(name) = names.get(*shouter) {
+                    log.log(LogEntry::Alert {
+                        alert: match pack_hunters.contains(*shouter) {
+                            true => format!("the {} howls, and the pack answers!", name.name),
+                            false => format!("the {} cries out, alerting its friends!", name.name),
+                        },
+                    });
+                }
+            }
+        }
+    }
+}
+
+// a random step, staying within range of home if there is one
+fn wander(
+    map: &mut Map,
+    pos: &mut Position,
+    rng: &mut RandomNumberGenerator,
+    home: Option<(Point, f32)>,
+) -> bool {
+    let step = Point::new(
+        pos.x + rng.roll_dice(1, 3) - 2,
+        pos.y + rng.roll_dice(1, 3) - 2,
+    );
+    if step.x < 1 || step.x > map.width - 2 || step.y < 1 || step.y > map.height - 2 {
+        return false;
+    }
+    if let Some((home, radius)) = home {
+        if rltk::DistanceAlg::Pythagoras.distance2d(step, home) > radius {
+            return false;
+        }
+    }
+    let step_idx = map.xy_idx(step.x, step.y);
+    if map.blocked[step_idx] {
+        return false;
+    }
+    let idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = false;
+    pos.x = step.x;
+    pos.y = step.y;
+    map.blocked[step_idx] = true;
+    true
+}
+
+// the open tile next to the quarry that's furthest from the rest of the pack, so they surround it
+fn flank_tile(
+    map: &Map,
+    here: Point,
+    quarry: Point,
+    pack: &[(Entity, Point)],
+    me: Entity,
+) -> Point {
+    let mut best = quarry;
+    let mut best_score = f32::MIN;
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let tile = Point::new(quarry.x + dx, quarry.y + dy);
+            if (dx == 0 && dy == 0)
+                || tile.x < 1
+                || tile.x > map.width - 2
+                || tile.y < 1
+                || tile.y > map.height - 2
+                || (tile != here && map.blocked[map.xy_idx(tile.x, tile.y)])
+            {
+                continue;
+            }
+            let spread = pack
+                .iter()
+                .filter(|(wolf, _)| *wolf != me)
+                .map(|(_, p)| rltk::DistanceAlg::Pythagoras.distance2d(*p, tile))
+                .fold(FLANK_SPREAD, f32::min);
+            let score = spread - rltk::DistanceAlg::Pythagoras.distance2d(here, tile) / 2.0;
+            if score > best_score {
+                best = tile;
+                best_score = score;
+            }
+        }
+    }
+    best
+}
+
+// takes the step that gets furthest from the threat, returns whether it moved
+fn step_away(map: &mut Map, pos: &mut Position, threat: Point) -> bool {
+    let here = Point::new(pos.x, pos.y);
+    let mut best = here;
+    let mut best_distance = rltk::DistanceAlg::Pythagoras.distance2d(here, threat);
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let step = Point::new(pos.x + dx, pos.y + dy);
+            if step.x < 1 || step.x > map.width - 2 || step.y < 1 || step.y > map.height - 2 {
+                continue;
+            }
+            if map.blocked[map.xy_idx(step.x, step.y)] {
+                continue;
+            }
+            let distance = rltk::DistanceAlg::Pythagoras.distance2d(step, threat);
+            if distance > best_distance {
+                best = step;
+                best_distance = distance;
+            }
+        }
+    }
+    if best == here {
+        return false;
+    }
+    let mut idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = false;
+    pos.x = best.x;
+    pos.y = best.y;
+    idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = true;
+    true
+}
+
+// takes one step along the path to the target, returns whether it moved
+fn step_toward(map: &mut Map, pos: &mut Position, target: Point) -> bool {
+    let path = rltk::a_star_search(
+        map.xy_idx(pos.x, pos.y) as i32,
+        map.xy_idx(target.x, target.y) as i32,
+        &mut *map,
+    );
+    if !path.success || path.steps.len() < 2 {
+        return false;
+    }
+    let mut idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = false;
+    pos.x = path.steps[1] as i32 % map.width;
+    pos.y = path.steps[1] as i32 / map.width;
+    idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lone_wolf_takes_the_nearest_side() {
+        let mut world = World::new();
+        let wolf = world.create_entity().build();
+        let map = Map::new(2);
+        let here = Point::new(23, 20);
+        let flank = flank_tile(&map, here, Point::new(20, 20), &[(wolf, here)], wolf);
+        assert_eq!(flank, Point::new(21, 20));
+    }
+
+    #[test]
+    fn pack_spreads_around_the_quarry() {
+        let mut world = World::new();
+        let wolf = world.create_entity().build();
+        let packmate = world.create_entity().build();
+        let mut map = Map::new(2);
+        let quarry = Point::new(20, 20);
+        let here = Point::new(23, 20);
+        let there = Point::new(21, 20);
+        let idx = map.xy_idx(there.x, there.y);
+        map.blocked[idx] = true;
+        let flank = flank_tile(&map, here, quarry, &[(wolf, here), (packmate, there)], wolf);
+        assert!(flank.x < quarry.x);
+    }
+
+    #[test]
+    fn only_open_tiles_inside_the_map() {
+        let mut world = World::new();
+        let wolf = world.create_entity().build();
+        let mut map = Map::new(2);
+        let quarry = Point::new(1, 1);
+        for (x, y) in [(2, 1), (2, 2)] {
+            let idx = map.xy_idx(x, y);
+            map.blocked[idx] = true;
+        }
+        let here = Point::new(5, 5);
+        let flank = flank_tile(&map, here, quarry, &[(wolf, here)], wolf);
+        assert_eq!(flank, Point::new(1, 2));
     }
 }
@@ -0,0 +1,139 @@
+use rltk::RandomNumberGenerator;
+
+use crate::stats::OverallStats;
+
+// placeholder npcs can put in their lines to (almost) say the player's name
+pub const NAME_PLACEHOLDER: &str = "{NAME}";
+
+const HONORIFICS: &[&str] = &[
+    "SIR", "DAME", "LORD", "LADY", "OLD", "LITTLE", "COUSIN", "BARON", "CHEF", "AUNTIE",
+];
+const RHYME_STARTS: &[&str] = &["B", "D", "FL", "GR", "M", "P", "SN", "W", "CR", "SPL"];
+const FOODS: &[&str] = &[
+    "MUFFIN", "CRUMB", "SCONE", "BUN", "TART", "DOUGH", "PUDDING", "BISCUIT", "TOAST", "PIE",
+    "CRUMPET", "STRUDEL",
+];
+const VOWELS: &[char] = &['A', 'E', 'I', 'O', 'U', 'Y'];
+
+// replaces the placeholder with how this speaker misremembers the player's name
+pub fn fill_name(text: &str, stats: &OverallStats, speaker: &str) -> String {
+    if !text.contains(NAME_PLACEHOLDER) {
+        return text.to_string();
+    }
+    let name = match stats.name.as_str() {
+        "" => "FRIEND".to_string(),
+        _ => misremember_name(&stats.name, stats.run_seed, speaker),
+    };
+    text.replace(NAME_PLACEHOLDER, &name)
+}
+
+// the same speaker gets the same name wrong the same way for the whole run
+pub fn misremember_name(name: &str, run_seed: u64, speaker: &str) -> String {
+    let name = name.trim().to_uppercase();
+    let mut rng = RandomNumberGenerator::seeded(run_seed ^ hash(speaker) ^ hash(&name));
+
+    let mut wrong_name = match rng.roll_dice(1, 3) {
+        1 => swap_letters(&name, &mut rng),
+        2 => rhyme(&name, &mut rng),
+        _ => food_pun(&name, &mut rng),
+    };
+    if wrong_name == name || rng.roll_dice(1, 2) == 1 {
+        wrong_name = format!("{} {}", pick(HONORIFICS, &mut rng), wrong_name);
+    }
+    wrong_name
+}
+
+fn swap_letters(name: &str, rng: &mut RandomNumberGenerator) -> String {
+    let mut chars: Vec<char> = name.chars().collect();
+    // keep the first letter, it's the part people usually remember
+    let swappable: Vec<usize> = (1..chars.len().saturating_sub(1))
+        .filter(|&i| chars[i] != chars[i + 1])
+        .collect();
+    match rng.random_slice_entry(&swappable) {
+        Some(&i) => {
+            chars.swap(i, i + 1);
+            chars.into_iter().collect()
+        }
+        None => name.to_string(),
+    }
+}
+
+fn rhyme(name: &str, rng: &mut RandomNumberGenerator) -> String {
+    let first_vowel = name.find(|c| VOWELS.contains(&c)).unwrap_or(0);
+    let onset = &name[..first_vowel];
+    let rest = &name[first_vowel..];
+    let options: Vec<&str> = RHYME_STARTS
+        .iter()
+        .copied()
+        .filter(|start| *start != onset)
+        .collect();
+    match rng.random_slice_entry(&options) {
+        Some(start) => format!("{}{}", start, rest),
+        None => name.to_string(),
+    }
+}
+
+fn food_pun(name: &str, rng: &mut RandomNumberGenerator) -> String {
+    // keep the name up through its first syllable, then bake the rest
+    let chars: Vec<char> = name.chars().collect();
+    let mut cut = chars
+        .iter()
+        .position(|c| VOWELS.contains(c))
+        .unwrap_or(chars.len());
+    while cut < chars.len() && VOWELS.contains(&chars[cut]) {
+        cut += 1;
+    }
+    cut = (cut + 1).min(chars.len());
+    let stem: String = chars[..cut].iter().collect();
+    format!("{}{}", stem, pick(FOODS, rng))
+}
+
+fn pick<'a>(options: &[&'a str], rng: &mut RandomNumberGenerator) -> &'a str {
+    options[(rng.roll_dice(1, options.len() as i32) - 1) as usize]
+}
+
+// fnv-1a, so the seed doesn't depend on std's randomized hasher
+fn hash(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_speaker_gets_it_wrong_the_same_way() {
+        assert_eq!(
+            misremember_name("BRAMBLE", 42, "THE WIZARD"),
+            misremember_name("bramble ", 42, "THE WIZARD")
+        );
+    }
+
+    #[test]
+    fn never_gets_it_right() {
+        for seed in 0..50 {
+            for name in ["BO", "ANNA", "MARGARET", "X", "ZZZ"] {
+                assert_ne!(misremember_name(name, seed, "FOREST DRUID"), name);
+            }
+        }
+    }
+
+    #[test]
+    fn fills_only_the_placeholder() {
+        let mut stats = OverallStats::new();
+        assert_eq!(fill_name("hello there", &stats, "PEP"), "hello there");
+        assert_eq!(fill_name("hi {NAME}!", &stats, "PEP"), "hi FRIEND!");
+        stats.name = "BRAMBLE".to_string();
+        let filled = fill_name("hi {NAME}!", &stats, "PEP");
+        assert!(!filled.contains(NAME_PLACEHOLDER));
+        assert_eq!(
+            filled,
+            format!("hi {}!", misremember_name("BRAMBLE", stats.run_seed, "PEP"))
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use rltk::Point;
+use specs::prelude::*;
+
+use crate::components::{Equipped, HeardNoise, HostileToPlayer, NoiseModifier, Position, Sneaking};
+
+pub const STEP_NOISE: i32 = 4;
+pub const FIGHT_NOISE: i32 = 8;
+pub const CHOP_NOISE: i32 = 10;
+pub const NEST_NOISE: i32 = 10;
+pub const THUD_NOISE: i32 = 6;
+
+struct NoiseRequest {
+    x: i32,
+    y: i32,
+    radius: i32,
+}
+
+// sounds made this round, heard by hostiles within the radius
+pub struct NoiseBuilder {
+    requests: Vec<NoiseRequest>,
+}
+
+impl NoiseBuilder {
+    pub fn new() -> NoiseBuilder {
+        NoiseBuilder {
+            requests: Vec::new(),
+        }
+    }
+
+    pub fn request(&mut self, x: i32, y: i32, radius: i32) {
+        self.requests.push(NoiseRequest { x, y, radius });
+    }
+}
+
+// how far a footstep carries, clunky armor makes it worse and sneaking halves it
+pub fn step_noise(ecs: &World, entity: Entity) -> i32 {
+    let equipped = ecs.read_storage::<Equipped>();
+    let modifiers = ecs.read_storage::<NoiseModifier>();
+    let mut noise = STEP_NOISE
+        + (&equipped, &modifiers)
+            .join()
+            .filter(|(eq, _)| eq.owner == entity)
+            .map(|(_, modifier)| modifier.amount)
+            .sum::<i32>();
+    if ecs.read_storage::<Sneaking>().contains(entity) {
+        noise /= 2;
+    }
+    i32::max(1, noise)
+}
+
+pub struct NoiseSystem {}
+
+impl<'a> System<'a> for NoiseSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteExpect<'a, NoiseBuilder>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, HostileToPlayer>,
+        WriteStorage<'a, HeardNoise>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut noise_builder, positions, hostiles, mut heard_noise) = data;
+
+        for noise in noise_builder.requests.iter() {
+            let origin = Point::new(noise.x, noise.y);
+            for (mob, pos, _hostile) in (&entities, &positions, &hostiles).join() {
+                let distance =
+                    rltk::DistanceAlg::Pythagoras.distance2d(origin, Point::new(pos.x, pos.y));
+                if distance < noise.radius as f32 {
+                    let _ = heard_noise.insert(
+                        mob,
+                        HeardNoise {
+                            x: noise.x,
+                            y: noise.y,
+                        },
+                    );
+                }
+            }
+        }
+
+        noise_builder.requests.clear();
+    }
+}
@@ -1,14 +1,21 @@
 use crate::{
     calculate_cake,
+    clear_path_system::player_cutting_power,
     components::{
-        CakeIngredient, Confusion, EntityMoved, GoodThyme, HungerClock, HungerState, Monster,
-        WantsToSwap,
+        CakeIngredient, Dialogue, EntityMoved, Equipped, GoodThyme, HoldsWater, HostileToPlayer,
+        HungerClock, HungerState, InBackpack, MeleeWeapon, Monster, Name, Persona, Sneaking,
+        StatusEffects, StatusKind, SufferDamage, ThirstClock, ThirstState, Vendor,
+        WantsToClearPath, WantsToSwap, WeaponTrait,
     },
+    converse::ConversationInput,
+    foraging,
     gamelog::LogEntry,
     get_visible_tooltips,
     map::TileType,
+    noise_system::{step_noise, NoiseBuilder},
     particle_system::ParticleBuilder,
     stats::{LevelStats, OverallStats},
+    thirst_system::{drank_murky_water, next_to_water, quench, water_is_murky},
     window_fx, IS_DEBUG_MODE_ACTIVE,
 };
 
@@ -16,10 +23,11 @@ use super::{
     gamelog::GameLog, CombatStats, Item, Map, Player, Position, RunState, State, Viewshed,
     WantsToMelee, WantsToPickupItem,
 };
-use rltk::{Point, Rltk, VirtualKeyCode};
+use rltk::{Point, RandomNumberGenerator, Rltk, VirtualKeyCode};
 use specs::prelude::*;
 
 pub fn try_move_player(dx: i32, dy: i32, ecs: &mut World) {
+    let stats = ecs.fetch::<OverallStats>();
     let mut positions = ecs.write_storage::<Position>();
     let mut players = ecs.write_storage::<Player>();
     let mut viewsheds = ecs.write_storage::<Viewshed>();
@@ -28,7 +36,7 @@ pub fn try_move_player(dx: i32, dy: i32, ecs: &mut World) {
     let entities = ecs.entities();
     let mut wants_to_melee = ecs.write_storage::<WantsToMelee>();
     let mut entity_moved = ecs.write_storage::<EntityMoved>();
-    let mut confused = ecs.write_storage::<Confusion>();
+    let statuses = ecs.read_storage::<StatusEffects>();
     let mut particle_builder = ecs.fetch_mut::<ParticleBuilder>();
     let mut wants_to_swap = ecs.write_storage::<WantsToSwap>();
     let mobs = ecs.read_storage::<Monster>();
@@ -36,26 +44,53 @@ pub fn try_move_player(dx: i32, dy: i32, ecs: &mut World) {
     for (entity, _player, pos, viewshed) in
         (&entities, &mut players, &mut positions, &mut viewsheds).join()
     {
-        if let Some(i_am_confused) = confused.get_mut(entity) {
-            i_am_confused.turns -= 1;
-            if i_am_confused.turns < 1 {
-                confused.remove(entity);
-            }
-
-            particle_builder.request(
-                pos.x,
-                pos.y,
-                rltk::RGB::named(rltk::PURPLE),
-                rltk::RGB::named(rltk::BLACK),
-                rltk::to_cp437('?'),
-                200.0,
-            );
+        let (mut dx, mut dy) = (dx, dy);
+        if let Some(status) = statuses.get(entity) {
+            let mut rng = ecs.fetch_mut::<RandomNumberGenerator>();
+            if status.has(StatusKind::Confused) {
+                particle_builder.request(
+                    pos.x,
+                    pos.y,
+                    rltk::RGB::named(rltk::PURPLE),
+                    rltk::RGB::named(rltk::BLACK),
+                    rltk::to_cp437('?'),
+                    200.0,
+                );
 
-            let mut gamelog = ecs.fetch_mut::<GameLog>();
-            gamelog.log(LogEntry::Alert {
-                alert: "YOU are CONFUSED and cannot move!".to_string(),
-            });
-            continue;
+                let mut gamelog = ecs.fetch_mut::<GameLog>();
+                gamelog.log(LogEntry::Alert {
+                    alert: format!("{} is CONFUSED and cannot move!", stats.name),
+                });
+                continue;
+            }
+            if status.has(StatusKind::Sleepy) && rng.roll_dice(1, 3) == 1 {
+                particle_builder.request(
+                    pos.x,
+                    pos.y,
+                    rltk::RGB::named(rltk::SLATE_BLUE),
+                    rltk::RGB::named(rltk::BLACK),
+                    rltk::to_cp437('z'),
+                    200.0,
+                );
+                ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                    notification: format!("{} nods off for a moment...", stats.name),
+                });
+                continue;
+            }
+            if status.has(StatusKind::High) && rng.roll_dice(1, 4) == 1 {
+                // feet have a mind of their own
+                dx = rng.roll_dice(1, 3) - 2;
+                dy = rng.roll_dice(1, 3) - 2;
+                ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                    notification: format!(
+                        "{} wanders off, distracted by the colours...",
+                        stats.name
+                    ),
+                });
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+            }
         }
 
         let destination_idx = map.xy_idx(pos.x + dx, pos.y + dy);
@@ -80,7 +115,28 @@ pub fn try_move_player(dx: i32, dy: i32, ecs: &mut World) {
             }
         }
 
+        // a long weapon pokes past an open or watery tile to whatever's on the far side
+        if map.tiles[destination_idx] != TileType::Wall {
+            if let Some(target) = reach_target(ecs, entity, pos.x + dx * 2, pos.y + dy * 2) {
+                wants_to_melee
+                    .insert(entity, WantsToMelee { target })
+                    .expect("add target failed");
+                return;
+            }
+        }
+
         if !map.blocked[destination_idx] {
+            // sneaking feet only get to move every other turn
+            let half_step = match ecs.write_storage::<Sneaking>().get_mut(entity) {
+                Some(sneaking) => {
+                    sneaking.half_step = !sneaking.half_step;
+                    sneaking.half_step
+                }
+                None => false,
+            };
+            if half_step {
+                continue;
+            }
             pos.x = pos.x + dx;
             pos.y = pos.y + dy;
             viewshed.dirty = true;
@@ -91,10 +147,57 @@ pub fn try_move_player(dx: i32, dy: i32, ecs: &mut World) {
                 .insert(entity, EntityMoved {})
                 .expect("should be able to add movement marker");
             ecs.write_resource::<LevelStats>().steps_taken += 1;
+            ecs.fetch_mut::<NoiseBuilder>()
+                .request(pos.x, pos.y, step_noise(ecs, entity));
+        } else if map.chop_progress.contains_key(&destination_idx) {
+            // keep hacking away at a half-cut tree
+            let _ = ecs.write_storage::<WantsToClearPath>().insert(
+                entity,
+                WantsToClearPath {
+                    x: pos.x + dx,
+                    y: pos.y + dy,
+                },
+            );
         }
     }
 }
 
+// something hostile at the far end of a reaching weapon, if one is held
+fn reach_target(ecs: &World, entity: Entity, x: i32, y: i32) -> Option<Entity> {
+    let has_reach = (
+        &ecs.read_storage::<Equipped>(),
+        &ecs.read_storage::<MeleeWeapon>(),
+    )
+        .join()
+        .any(|(eq, weapon)| eq.owner == entity && weapon.traits.contains(&WeaponTrait::Reach));
+    let map = ecs.fetch::<Map>();
+    if !has_reach || x < 0 || x >= map.width || y < 0 || y >= map.height {
+        return None;
+    }
+    let hostiles = ecs.read_storage::<HostileToPlayer>();
+    map.tile_content[map.xy_idx(x, y)]
+        .iter()
+        .find(|e| hostiles.contains(**e))
+        .copied()
+}
+
+fn toggle_sneaking(ecs: &mut World) {
+    let player_entity = *ecs.fetch::<Entity>();
+    let mut sneaking = ecs.write_storage::<Sneaking>();
+    let mut log = ecs.fetch_mut::<GameLog>();
+    let stats = ecs.fetch::<OverallStats>();
+    if sneaking.remove(player_entity).is_some() {
+        log.log(LogEntry::Notification {
+            notification: format!("{} stops sneaking.", stats.name),
+        });
+    } else {
+        let _ = sneaking.insert(player_entity, Sneaking { half_step: false });
+        log.log(LogEntry::Notification {
+            notification: format!("{} starts sneaking.. quieter, but slower.", stats.name),
+        });
+    }
+}
+
 pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
     match ctx.key {
         None => return RunState::CoreAwaitingInput,
@@ -119,7 +222,35 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
 
             VirtualKeyCode::I => return RunState::MenuInventory,
             VirtualKeyCode::D => return RunState::MenuDropItem,
+            VirtualKeyCode::F => return RunState::MenuThrowItem,
             VirtualKeyCode::E => return RunState::MenuRemoveItem,
+            VirtualKeyCode::C => {
+                if player_cutting_power(&gs.ecs).is_none() {
+                    gs.ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                        notification: format!(
+                            "{} needs something sharp to cut through the trees, like a HATCHET.",
+                            gs.ecs.fetch::<OverallStats>().name
+                        ),
+                    });
+                    return RunState::CoreAwaitingInput;
+                }
+                return RunState::MenuClearPath;
+            }
+            VirtualKeyCode::R => return RunState::MenuCrafting,
+            VirtualKeyCode::P => return RunState::MenuShove,
+            VirtualKeyCode::S => {
+                toggle_sneaking(&mut gs.ecs);
+                return RunState::CoreAwaitingInput;
+            }
+            VirtualKeyCode::T => match try_talk(&mut gs.ecs) {
+                Some(talk) => return talk,
+                None => {
+                    gs.ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+                        notification: "nobody nearby wants to talk..".to_string(),
+                    });
+                    return RunState::CoreAwaitingInput;
+                }
+            },
 
             VirtualKeyCode::Return => {
                 return RunState::ActionShowObjects {
@@ -164,7 +295,7 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
                             row: 0,
                             iteration: 0,
                         };
-                    } else {
+                    } else if !foraging::try_harvest(&mut gs.ecs) && !try_drink(&mut gs.ecs) {
                         skip_turn(&mut gs.ecs);
                     }
                 }
@@ -215,6 +346,13 @@ fn skip_turn(ecs: &mut World) -> RunState {
                 _ => {}
             }
         }
+        let thirst_clocks = ecs.read_storage::<ThirstClock>();
+        if let Some(tc) = thirst_clocks.get(*player_entity) {
+            match tc.state {
+                ThirstState::Thirsty | ThirstState::Parched => can_heal = false,
+                _ => {}
+            }
+        }
 
         if can_heal {
             let mut health_components = ecs.write_storage::<CombatStats>();
@@ -233,6 +371,117 @@ fn skip_turn(ecs: &mut World) -> RunState {
     RunState::CorePlayerTurn
 }
 
+fn try_talk(ecs: &mut World) -> Option<RunState> {
+    let npc;
+    let has_dialogue;
+    let is_vendor;
+    {
+        let player_pos = ecs.fetch::<Point>();
+        let entities = ecs.entities();
+        let dialogues = ecs.read_storage::<Dialogue>();
+        let personas = ecs.read_storage::<Persona>();
+        let vendors = ecs.read_storage::<Vendor>();
+        let positions = ecs.read_storage::<Position>();
+
+        // talk to whoever is standing right next to you
+        npc = (&entities, &positions)
+            .join()
+            .filter(|(npc, _)| {
+                dialogues.contains(*npc) || vendors.contains(*npc) || personas.contains(*npc)
+            })
+            .find(|(_, pos)| {
+                i32::abs(pos.x - player_pos.x) <= 1 && i32::abs(pos.y - player_pos.y) <= 1
+            })
+            .map(|(npc, _)| npc)?;
+        has_dialogue = dialogues.contains(npc);
+        is_vendor = vendors.contains(npc);
+    }
+    if has_dialogue {
+        return Some(RunState::MenuDialogue { npc, node: 0 });
+    }
+    if is_vendor {
+        return Some(RunState::MenuBarter {
+            vendor: npc,
+            wanted: -1,
+        });
+    }
+    ecs.fetch_mut::<ConversationInput>().line.clear();
+    Some(RunState::MenuConverse { npc })
+}
+
+// fills a carried bucket if there's room, otherwise drinks straight from the water
+fn try_drink(ecs: &mut World) -> bool {
+    let player_entity = *ecs.fetch::<Entity>();
+    let murky;
+    {
+        let map = ecs.fetch::<Map>();
+        if !next_to_water(&map, *ecs.fetch::<Point>()) {
+            return false;
+        }
+        murky = water_is_murky(&map);
+    }
+
+    let filled = {
+        let entities = ecs.entities();
+        let backpack_items = ecs.read_storage::<InBackpack>();
+        let names = ecs.read_storage::<Name>();
+        let mut holds_water = ecs.write_storage::<HoldsWater>();
+        match (&entities, &mut holds_water, &backpack_items)
+            .join()
+            .find(|(_, water, pack)| pack.owner == player_entity && water.sips < water.capacity)
+        {
+            Some((container, water, _)) => {
+                water.sips = water.capacity;
+                water.contaminated = water.contaminated || murky;
+                Some(
+                    names
+                        .get(container)
+                        .map_or("BUCKET".to_string(), |name| name.name.clone()),
+                )
+            }
+            None => None,
+        }
+    };
+    let player_name = ecs.fetch::<OverallStats>().name.clone();
+    if let Some(container) = filled {
+        ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+            notification: match murky {
+                true => format!("{} fills the {} with murky water.", player_name, container),
+                false => format!("{} fills the {} with clear water.", player_name, container),
+            },
+        });
+        return true;
+    }
+
+    let quenched = match ecs.read_storage::<ThirstClock>().get(player_entity) {
+        Some(tc) => tc.state == ThirstState::Quenched,
+        None => true,
+    };
+    if quenched {
+        return false;
+    }
+    if let Some(tc) = ecs.write_storage::<ThirstClock>().get_mut(player_entity) {
+        quench(tc);
+    }
+    ecs.fetch_mut::<LevelStats>().water_drunk += 1;
+    ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+        notification: format!(
+            "{} cups their hands and drinks from the water.",
+            player_name
+        ),
+    });
+    if murky {
+        drank_murky_water(
+            player_entity,
+            Some(&player_name),
+            &mut ecs.fetch_mut::<GameLog>(),
+            &mut ecs.write_storage::<SufferDamage>(),
+            &mut ecs.fetch_mut::<LevelStats>(),
+        );
+    }
+    true
+}
+
 fn try_next_level(ecs: &mut World) -> bool {
     let player_pos = ecs.fetch::<Point>();
     let map = ecs.fetch::<Map>();
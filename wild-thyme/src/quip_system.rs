@@ -1,7 +1,9 @@
 use crate::{
     components::{Name, Quips},
     gamelog::{GameLog, LogEntry},
+    name_mangler::fill_name,
     particle_system::ParticleBuilder,
+    stats::OverallStats,
     RunState,
 };
 
@@ -20,11 +22,21 @@ impl<'a> System<'a> for QuipSystem {
         WriteStorage<'a, Quips>,
         ReadStorage<'a, Position>,
         WriteExpect<'a, ParticleBuilder>,
+        ReadExpect<'a, OverallStats>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (runstate, mut rng, map, mut log, names, mut quips, positions, mut particle_builder) =
-            data;
+        let (
+            runstate,
+            mut rng,
+            map,
+            mut log,
+            names,
+            mut quips,
+            positions,
+            mut particle_builder,
+            stats,
+        ) = data;
 
         if *runstate != RunState::CorePostRound {
             return;
@@ -44,7 +56,7 @@ impl<'a> System<'a> for QuipSystem {
             if let Some(quip) = rng.random_slice_entry(&quips.quips) {
                 log.log(LogEntry::Quip {
                     subject: format!("{}", name.name),
-                    quip: format!("{}", quip),
+                    quip: fill_name(quip, &stats, &name.name),
                 });
                 particle_builder.request(
                     pos.x,
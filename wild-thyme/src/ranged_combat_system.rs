@@ -0,0 +1,183 @@
+use crate::{
+    components::{
+        Backpack, CombatStats, InBackpack, Name, Position, RangedAttack, RangedEffect,
+        SufferDamage, WantsToShoot,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    particle_system::ParticleBuilder,
+};
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+pub struct RangedCombatSystem {}
+
+impl<'a> System<'a> for RangedCombatSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, WantsToShoot>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, RangedAttack>,
+        WriteStorage<'a, SufferDamage>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, InBackpack>,
+        WriteStorage<'a, Backpack>,
+        ReadExpect<'a, Map>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut log,
+            mut wants_shoot,
+            names,
+            combat_stats,
+            ranged_attacks,
+            mut inflict_damage,
+            mut particle_builder,
+            mut positions,
+            mut backpack_items,
+            mut backpacks,
+            map,
+            mut rng,
+        ) = data;
+
+        for (entity, wants_shoot, name, stats, ranged) in (
+            &entities,
+            &wants_shoot,
+            &names,
+            &combat_stats,
+            &ranged_attacks,
+        )
+            .join()
+        {
+            if stats.hp <= 0 {
+                continue;
+            }
+            let target_alive = match combat_stats.get(wants_shoot.target) {
+                Some(target_stats) => target_stats.hp > 0,
+                None => false,
+            };
+            if !target_alive {
+                continue;
+            }
+            let (from, to) = match (positions.get(entity), positions.get(wants_shoot.target)) {
+                (Some(from), Some(to)) => (Point::new(from.x, from.y), Point::new(to.x, to.y)),
+                _ => continue,
+            };
+            let target_name = names
+                .get(wants_shoot.target)
+                .map(|name| name.name.clone())
+                .unwrap_or_default();
+
+            // the projectile lingers longer the closer it gets to the target
+            for (i, step) in rltk::line2d(rltk::LineAlg::Bresenham, from, to)
+                .iter()
+                .skip(1)
+                .enumerate()
+            {
+                particle_builder.request(
+                    step.x,
+                    step.y,
+                    ranged.color,
+                    rltk::RGB::named(rltk::BLACK),
+                    ranged.glyph,
+                    60.0 + 40.0 * i as f32,
+                );
+            }
+
+            match ranged.effect {
+                RangedEffect::Damage => {
+                    log.log(LogEntry::Action {
+                        subject: name.name.clone(),
+                        verb: format!("shot"),
+                        object: target_name,
+                        suffix: format!("for {} HP!", ranged.damage),
+                    });
+                    SufferDamage::new_damage(
+                        &mut inflict_damage,
+                        wants_shoot.target,
+                        ranged.damage,
+                    );
+                }
+                RangedEffect::KnockItemLoose => {
+                    let carried: Vec<Entity> = (&entities, &backpack_items)
+                        .join()
+                        .filter(|(_, pack)| pack.owner == wants_shoot.target)
+                        .map(|(item, _)| item)
+                        .collect();
+                    let item = match rng.random_slice_entry(&carried) {
+                        Some(item) => *item,
+                        None => {
+                            log.log(LogEntry::Action {
+                                subject: name.name.clone(),
+                                verb: format!("zapped"),
+                                object: target_name,
+                                suffix: format!("but there was nothing to knock loose."),
+                            });
+                            continue;
+                        }
+                    };
+
+                    // the item lands on a free tile next to the target if there is one
+                    let mut landing = to;
+                    let mut free_tiles = Vec::new();
+                    for dx in -1..=1 {
+                        for dy in -1..=1 {
+                            let (x, y) = (to.x + dx, to.y + dy);
+                            if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 {
+                                continue;
+                            }
+                            if !map.blocked[map.xy_idx(x, y)] {
+                                free_tiles.push(Point::new(x, y));
+                            }
+                        }
+                    }
+                    if let Some(free) = rng.random_slice_entry(&free_tiles) {
+                        landing = *free;
+                    }
+
+                    positions
+                        .insert(
+                            item,
+                            Position {
+                                x: landing.x,
+                                y: landing.y,
+                            },
+                        )
+                        .expect("should be able to add position for knocked loose item");
+                    backpack_items.remove(item);
+                    if let Some(backpack) = backpacks.get_mut(wants_shoot.target) {
+                        backpack.items -= 1;
+                    }
+                    particle_builder.request(
+                        landing.x,
+                        landing.y,
+                        ranged.color,
+                        rltk::RGB::named(rltk::BLACK),
+                        rltk::to_cp437('!'),
+                        400.0,
+                    );
+                    log.log(LogEntry::Action {
+                        subject: name.name.clone(),
+                        verb: format!("zapped"),
+                        object: target_name,
+                        suffix: format!(
+                            "and knocked the {} loose!",
+                            names
+                                .get(item)
+                                .map(|name| name.name.clone())
+                                .unwrap_or_default()
+                        ),
+                    });
+                }
+            }
+        }
+
+        wants_shoot.clear();
+    }
+}
@@ -7,6 +7,9 @@ use specs::saveload::{
 use std::fs::{self, File};
 use std::path::Path;
 
+use crate::day_night_system::WorldClock;
+use crate::stats::OverallStats;
+
 macro_rules! serialize_individually {
     ($ecs:expr, $ser:expr, $data:expr, $( $type:ty),*) => {
         $(
@@ -27,9 +30,16 @@ pub fn save_game(ecs: &mut World) {
         .get_mut::<super::map::Map>()
         .expect("map should always exist")
         .clone();
+    // the seed keeps everyone misremembering the name the same way after a load
+    let run_seed = ecs.fetch::<OverallStats>().run_seed;
+    let clock = ecs.fetch::<WorldClock>().clone();
     let savehelper = ecs
         .create_entity()
-        .with(SerializationHelper { map: mapcopy })
+        .with(SerializationHelper {
+            map: mapcopy,
+            run_seed,
+            clock,
+        })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 
@@ -60,7 +70,6 @@ pub fn save_game(ecs: &mut World) {
             Ranged,
             InflictsDamage,
             AreaOfEffect,
-            Confusion,
             ProvidesHealing,
             InBackpack,
             WantsToPickupItem,
@@ -70,7 +79,6 @@ pub fn save_game(ecs: &mut World) {
             SerializationHelper,
             Equippable,
             Equipped,
-            MeleePowerBonus,
             DefenseBonus,
             ParticleLifetime,
             HungerClock,
@@ -90,7 +98,48 @@ pub fn save_game(ecs: &mut World) {
             Quips,
             Backpack,
             GoodThyme,
-            CakeIngredient // new component register here
+            CakeIngredient,
+            Dialogue,
+            Persona,
+            Worth,
+            Vendor,
+            RangedAttack,
+            WantsToShoot,
+            ThirstClock,
+            HoldsWater,
+            PurifiesWater,
+            Nocturnal,
+            LightSource,
+            Warm,
+            Perishable,
+            SlowsSpoilage,
+            ForagingGear,
+            Harvestable,
+            HeardNoise,
+            CutsTrees,
+            WantsToClearPath,
+            Ignites,
+            KnownRecipes,
+            Predator,
+            Scavenger,
+            Morale,
+            Behaviour,
+            AiMemory,
+            NoiseModifier,
+            Sneaking,
+            Initiative,
+            StatusEffects,
+            InflictsStatus,
+            MeleeWeapon,
+            WantsToShove,
+            Knockback,
+            KnocksBack,
+            WantsToThrow,
+            Weight,
+            Fragile,
+            Bait,
+            PackHunter,
+            WizardBoss // new component register here
         );
     }
 
@@ -162,7 +211,6 @@ pub fn load_game(ecs: &mut World) {
             Ranged,
             InflictsDamage,
             AreaOfEffect,
-            Confusion,
             ProvidesHealing,
             InBackpack,
             WantsToPickupItem,
@@ -172,7 +220,6 @@ pub fn load_game(ecs: &mut World) {
             SerializationHelper,
             Equippable,
             Equipped,
-            MeleePowerBonus,
             DefenseBonus,
             ParticleLifetime,
             HungerClock,
@@ -192,7 +239,48 @@ pub fn load_game(ecs: &mut World) {
             Quips,
             Backpack,
             GoodThyme,
-            CakeIngredient // new component register here
+            CakeIngredient,
+            Dialogue,
+            Persona,
+            Worth,
+            Vendor,
+            RangedAttack,
+            WantsToShoot,
+            ThirstClock,
+            HoldsWater,
+            PurifiesWater,
+            Nocturnal,
+            LightSource,
+            Warm,
+            Perishable,
+            SlowsSpoilage,
+            ForagingGear,
+            Harvestable,
+            HeardNoise,
+            CutsTrees,
+            WantsToClearPath,
+            Ignites,
+            KnownRecipes,
+            Predator,
+            Scavenger,
+            Morale,
+            Behaviour,
+            AiMemory,
+            NoiseModifier,
+            Sneaking,
+            Initiative,
+            StatusEffects,
+            InflictsStatus,
+            MeleeWeapon,
+            WantsToShove,
+            Knockback,
+            KnocksBack,
+            WantsToThrow,
+            Weight,
+            Fragile,
+            Bait,
+            PackHunter,
+            WizardBoss // new component register here
         );
     }
 
@@ -206,6 +294,8 @@ pub fn load_game(ecs: &mut World) {
             let mut worldmap = ecs.write_resource::<super::map::Map>();
             *worldmap = h.map.clone();
             worldmap.tile_content = vec![Vec::new(); super::map::MAPCOUNT];
+            ecs.write_resource::<OverallStats>().run_seed = h.run_seed;
+            *ecs.write_resource::<WorldClock>() = h.clock.clone();
             deleteme = Some(e);
         }
         for (e, _p, pos) in (&entities, &player, &position).join() {
@@ -0,0 +1,261 @@
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        BlocksTile, CombatStats, EntityMoved, Item, Knockback, Name, Position, StatusEffects,
+        StatusKind, SufferDamage, Viewshed, WantsToShove,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    particle_system::ParticleBuilder,
+    stats::LevelStats,
+};
+
+const COLLISION_DAMAGE: i32 = 2;
+const DUNK_TURNS: i32 = 4;
+
+// turns a shove into a knockback, if the shover is strong enough
+pub struct ShoveSystem {}
+
+impl<'a> System<'a> for ShoveSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Map>,
+        WriteStorage<'a, WantsToShove>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, Knockback>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            map,
+            mut wants_shove,
+            positions,
+            combat_stats,
+            items,
+            names,
+            mut knockbacks,
+            mut rng,
+            mut log,
+            mut level_stats,
+        ) = data;
+        let player_name = &names
+            .get(*player_entity)
+            .expect("player should always have Name")
+            .name;
+
+        for (entity, shove, pos) in (&entities, &wants_shove, &positions).join() {
+            let idx = map.xy_idx(shove.x, shove.y);
+            // creatures get shoved before anything lying underfoot
+            let target = map.tile_content[idx]
+                .iter()
+                .find(|e| combat_stats.contains(**e))
+                .or_else(|| map.tile_content[idx].iter().find(|e| items.contains(**e)));
+            let target = match target {
+                Some(target) => *target,
+                None => {
+                    if entity == *player_entity {
+                        log.log(LogEntry::Notification {
+                            notification: format!("{} shoves at thin air.", player_name),
+                        });
+                    }
+                    continue;
+                }
+            };
+            let target_name = names
+                .get(target)
+                .map_or("thing".to_string(), |name| name.name.clone());
+
+            // bigger things push back
+            if let (Some(stats), Some(target_stats)) =
+                (combat_stats.get(entity), combat_stats.get(target))
+            {
+                if rng.roll_dice(1, 20) + stats.power < 10 + target_stats.power {
+                    if entity == *player_entity {
+                        log.log(LogEntry::Action {
+                            subject: player_name.clone(),
+                            verb: format!("shoves"),
+                            object: target_name,
+                            suffix: format!(", but it doesn't budge."),
+                        });
+                    }
+                    continue;
+                }
+            }
+
+            let _ = knockbacks.insert(
+                target,
+                Knockback {
+                    dx: i32::signum(shove.x - pos.x),
+                    dy: i32::signum(shove.y - pos.y),
+                    force: 1,
+                },
+            );
+            if entity == *player_entity {
+                level_stats.things_shoved += 1;
+                log.log(LogEntry::Action {
+                    subject: player_name.clone(),
+                    verb: format!("shoves"),
+                    object: target_name,
+                    suffix: format!("!"),
+                });
+            }
+        }
+
+        wants_shove.clear();
+    }
+}
+
+// moves knocked-back things a tile at a time until they hit something
+pub struct KnockbackSystem {}
+
+impl<'a> System<'a> for KnockbackSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, Point>,
+        WriteExpect<'a, Map>,
+        WriteStorage<'a, Knockback>,
+        WriteStorage<'a, Position>,
+        ReadStorage<'a, BlocksTile>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, EntityMoved>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, StatusEffects>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, GameLog>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut player_pos,
+            mut map,
+            mut knockbacks,
+            mut positions,
+            blocks_tile,
+            combat_stats,
+            names,
+            mut suffer_damage,
+            mut entity_moved,
+            mut viewsheds,
+            mut statuses,
+            mut particle_builder,
+            mut log,
+        ) = data;
+
+        let mut sunk: Vec<Entity> = Vec::new();
+        for (entity, knockback, pos) in (&entities, &knockbacks, &mut positions).join() {
+            let name = names
+                .get(entity)
+                .map_or("thing".to_string(), |name| name.name.clone());
+            let is_creature = combat_stats.contains(entity);
+            for _ in 0..knockback.force {
+                let (x, y) = (pos.x + knockback.dx, pos.y + knockback.dy);
+                if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 {
+                    break;
+                }
+                let idx = map.xy_idx(x, y);
+                let seen = map.visible_tiles[idx] || map.visible_tiles[map.xy_idx(pos.x, pos.y)];
+
+                // water takes items for good and gives creatures a dunking
+                if map.tiles[idx] == TileType::Water {
+                    if is_creature {
+                        SufferDamage::new_damage(&mut suffer_damage, entity, 1);
+                        StatusEffects::inflict(
+                            &mut statuses,
+                            entity,
+                            StatusKind::Slowed,
+                            DUNK_TURNS,
+                        );
+                        if seen {
+                            log.log(LogEntry::Notification {
+                                notification: format!(
+                                    "the {} is dunked in the WATER and crawls out soaked!",
+                                    name
+                                ),
+                            });
+                        }
+                    } else {
+                        sunk.push(entity);
+                        if seen {
+                            log.log(LogEntry::Notification {
+                                notification: format!("the {} sinks into the WATER.", name),
+                            });
+                        }
+                    }
+                    break;
+                }
+
+                // trees and anyone standing in the way, stumps are low enough to tumble over
+                let obstacle = match map.tiles[idx] {
+                    TileType::Wall => Some("the trees".to_string()),
+                    _ => map.tile_content[idx]
+                        .iter()
+                        .find(|e| blocks_tile.contains(**e) && combat_stats.contains(**e))
+                        .map(|other| {
+                            SufferDamage::new_damage(&mut suffer_damage, *other, 1);
+                            names
+                                .get(*other)
+                                .map_or("something".to_string(), |name| name.name.clone())
+                        }),
+                };
+                if let Some(obstacle) = obstacle {
+                    if is_creature {
+                        SufferDamage::new_damage(&mut suffer_damage, entity, COLLISION_DAMAGE);
+                        particle_builder.request(
+                            x,
+                            y,
+                            rltk::RGB::named(rltk::ORANGE),
+                            rltk::RGB::named(rltk::BLACK),
+                            rltk::to_cp437('‼'),
+                            200.0,
+                        );
+                        if seen {
+                            log.log(LogEntry::Alert {
+                                alert: format!("the {} slams into {}!", name, obstacle),
+                            });
+                        }
+                    }
+                    break;
+                }
+
+                if blocks_tile.contains(entity) {
+                    let old_idx = map.xy_idx(pos.x, pos.y);
+                    map.blocked[old_idx] = false;
+                    map.blocked[idx] = true;
+                }
+                pos.x = x;
+                pos.y = y;
+                let _ = entity_moved.insert(entity, EntityMoved {});
+                if let Some(viewshed) = viewsheds.get_mut(entity) {
+                    viewshed.dirty = true;
+                }
+                if entity == *player_entity {
+                    player_pos.x = x;
+                    player_pos.y = y;
+                }
+            }
+        }
+
+        knockbacks.clear();
+        for item in sunk {
+            entities
+                .delete(item)
+                .expect("should be able to delete sunken item");
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::components::*;
+use rltk::{RandomNumberGenerator, RGB};
+use specs::prelude::*;
+use specs::saveload::{MarkedBuilder, SimpleMarker};
+
+pub fn berry_shrub(ecs: &mut World, x: i32, y: i32) {
+    feature(
+        ecs,
+        x,
+        y,
+        ForageSpec {
+            glyph: rltk::to_cp437('♠'),
+            fg: RGB::from_hex("#a04080").expect("hardcoded"),
+            name: "BERRY SHRUB",
+            tool: ForagingTool::Basket,
+            yields: "GOODBERRY",
+        },
+    );
+}
+
+pub fn root_patch(ecs: &mut World, x: i32, y: i32) {
+    feature(
+        ecs,
+        x,
+        y,
+        ForageSpec {
+            glyph: rltk::to_cp437('"'),
+            fg: RGB::from_hex("#b09060").expect("hardcoded"),
+            name: "ROOT PATCH",
+            tool: ForagingTool::Digging,
+            yields: "WILD GINGER",
+        },
+    );
+}
+
+pub fn beehive(ecs: &mut World, x: i32, y: i32) {
+    feature(
+        ecs,
+        x,
+        y,
+        ForageSpec {
+            glyph: rltk::to_cp437('☼'),
+            fg: RGB::from_hex("#e0b030").expect("hardcoded"),
+            name: "BEEHIVE",
+            tool: ForagingTool::Smoker,
+            yields: "HONEYCOMB",
+        },
+    );
+}
+
+pub fn mushroom_ring(ecs: &mut World, x: i32, y: i32) {
+    feature(
+        ecs,
+        x,
+        y,
+        ForageSpec {
+            glyph: rltk::to_cp437('○'),
+            fg: RGB::from_hex("#c09070").expect("hardcoded"),
+            name: "MUSHROOM RING",
+            tool: ForagingTool::Basket,
+            yields: "MOREL MUSHROOM",
+        },
+    );
+}
+
+pub fn nesting_tree(ecs: &mut World, x: i32, y: i32) {
+    feature(
+        ecs,
+        x,
+        y,
+        ForageSpec {
+            glyph: rltk::to_cp437('♣'),
+            fg: RGB::from_hex("#60a040").expect("hardcoded"),
+            name: "NESTING TREE",
+            tool: ForagingTool::Basket,
+            yields: "EGG",
+        },
+    );
+}
+
+// how a feature looks, and what it gives up to which tool
+struct ForageSpec {
+    glyph: rltk::FontCharType,
+    fg: RGB,
+    name: &'static str,
+    tool: ForagingTool,
+    yields: &'static str,
+}
+
+fn feature(ecs: &mut World, x: i32, y: i32, spec: ForageSpec) {
+    let harvests = ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(1, 3);
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: spec.glyph,
+            fg: spec.fg,
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: spec.name.to_string(),
+        })
+        .with(Harvestable {
+            tool: spec.tool,
+            yields: spec.yields.to_string(),
+            harvests,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
@@ -16,6 +16,8 @@ pub fn rock(ecs: &mut World, x: i32, y: i32) {
             name: "ROCK".to_string(),
         })
         .with(Item {})
+        .with(Weight { weight: 2 })
+        .with(Worth { value: 1 })
         .with(Consumable {})
         .with(Ranged { range: 6 })
         .with(InflictsDamage { damage: 7 })
@@ -47,6 +49,7 @@ pub fn dart_gun(ecs: &mut World, x: i32, y: i32) {
             name: "DART GUN".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 10 })
         .with(Rare {})
         .with(Ranged { range: 6 })
         .with(InflictsDamage { damage: 3 })
@@ -67,11 +70,17 @@ pub fn sparkling_powder(ecs: &mut World, x: i32, y: i32) {
             name: "SPARKLING POWDER".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 8 })
         .with(Rare {})
         .with(Consumable {})
         .with(Ranged { range: 6 })
         .with(InflictsDamage { damage: 20 })
         .with(AreaOfEffect { radius: 3 })
+        .with(Ignites {})
+        .with(InflictsStatus {
+            kind: StatusKind::Glowing,
+            turns: 20,
+        })
         .with(CakeIngredient {
             adjective: "SHINY".to_string(),
             super_adjective: "FLAMING".to_string(),
@@ -100,9 +109,13 @@ pub fn confusion_scroll(ecs: &mut World, x: i32, y: i32) {
             name: "WEIRD CONFUSING POWDER".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 5 })
         .with(Consumable {})
         .with(Ranged { range: 6 })
-        .with(Confusion { turns: 4 })
+        .with(InflictsStatus {
+            kind: StatusKind::Confused,
+            turns: 4,
+        })
         .with(CakeIngredient {
             adjective: "ODD".to_string(),
             super_adjective: "BIZARRE".to_string(),
@@ -131,6 +144,7 @@ pub fn healing_herbs(ecs: &mut World, x: i32, y: i32) {
             name: "HEALING HERBS".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 4 })
         .with(ProvidesHealing { heal_amount: 8 })
         .with(Consumable {})
         .with(CakeIngredient {
@@ -176,7 +190,9 @@ pub fn meat(ecs: &mut World) -> Entity {
         .with(Name {
             name: "RAW MEAT".to_string(),
         })
+        .with(Perishable::new("RAW MEAT", 300))
         .with(Item {})
+        .with(Worth { value: 2 })
         .with(ProvidesHealing { heal_amount: -2 })
         .with(ProvidesFood {})
         .with(Consumable {})
@@ -223,10 +239,19 @@ pub fn milk(ecs: &mut World) -> Entity {
         .with(Name {
             name: "MILK".to_string(),
         })
+        .with(Perishable::new("MILK", 400))
         .with(Item {})
+        .with(Fragile {
+            verb: "spills everywhere".to_string(),
+        })
+        .with(Worth { value: 3 })
         .with(ProvidesHealing { heal_amount: 2 })
         .with(ProvidesFood {})
         .with(Consumable {})
+        .with(InflictsStatus {
+            kind: StatusKind::Sleepy,
+            turns: 8,
+        })
         .with(CakeIngredient {
             adjective: "DELICIOUS".to_string(),
             super_adjective: "CREAMY".to_string(),
@@ -270,7 +295,12 @@ pub fn egg(ecs: &mut World) -> Entity {
         .with(Name {
             name: "EGG".to_string(),
         })
+        .with(Perishable::new("EGG", 600))
         .with(Item {})
+        .with(Fragile {
+            verb: "splatters".to_string(),
+        })
+        .with(Worth { value: 3 })
         .with(ProvidesHealing { heal_amount: 10 })
         .with(ProvidesFood {})
         .with(Consumable {})
@@ -302,10 +332,21 @@ pub fn pointy_stick(ecs: &mut World, x: i32, y: i32) {
             name: "POINTY STICK".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 4 })
         .with(Equippable {
             slot: EquipmentSlot::Melee,
         })
-        .with(MeleePowerBonus { power: 2 })
+        .with(MeleeWeapon {
+            n_dice: 1,
+            die_type: 3,
+            bonus: 0,
+            hit_bonus: 1,
+            traits: Vec::new(),
+        })
+        .with(ForagingGear {
+            tool: ForagingTool::Digging,
+            quality: 1,
+        })
         .with(CakeIngredient {
             adjective: "SHARP".to_string(),
             super_adjective: "EXTRA POINTY".to_string(),
@@ -334,11 +375,23 @@ pub fn cake_knife(ecs: &mut World, x: i32, y: i32) {
             name: "CAKE KNIFE".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 12 })
         .with(Rare {})
         .with(Equippable {
             slot: EquipmentSlot::Melee,
         })
-        .with(MeleePowerBonus { power: 4 })
+        .with(MeleeWeapon {
+            n_dice: 1,
+            die_type: 6,
+            bonus: 1,
+            hit_bonus: 2,
+            traits: Vec::new(),
+        })
+        .with(ForagingGear {
+            tool: ForagingTool::Digging,
+            quality: 2,
+        })
+        .with(CutsTrees { power: 1 })
         .with(CakeIngredient {
             adjective: "DANGEROUS".to_string(),
             super_adjective: "DEADLY".to_string(),
@@ -367,11 +420,14 @@ pub fn bark_armor(ecs: &mut World, x: i32, y: i32) {
             name: "BARK ARMOR".to_string(),
         })
         .with(Item {})
+        .with(Weight { weight: 3 })
+        .with(Worth { value: 8 })
         .with(Rare {})
         .with(Equippable {
             slot: EquipmentSlot::Shield,
         })
         .with(DefenseBonus { defense: 1 })
+        .with(NoiseModifier { amount: 2 })
         .with(CakeIngredient {
             adjective: "SHELLED".to_string(),
             super_adjective: "DRY AF".to_string(),
@@ -399,7 +455,9 @@ pub fn goodberry(ecs: &mut World, x: i32, y: i32) {
         .with(Name {
             name: "GOODBERRY".to_string(),
         })
+        .with(Perishable::new("GOODBERRY", 500))
         .with(Item {})
+        .with(Worth { value: 3 })
         .with(ProvidesFood {})
         .with(Consumable {})
         .with(CakeIngredient {
@@ -430,6 +488,7 @@ pub fn thyme(ecs: &mut World, x: i32, y: i32) {
             name: "GOOD THYME".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 25 })
         .with(Rare {})
         .with(GoodThyme {})
         .with(ProvidesFood {})
@@ -450,13 +509,46 @@ pub fn thyme(ecs: &mut World, x: i32, y: i32) {
         .build();
 }
 
+// the wizard's prize, and the makings of the best cake in the land
+pub fn ancient_thyme(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('¥'),
+            fg: RGB::from_hex("#f0d060").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "ANCIENT THYME".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 100 })
+        .with(Rare {})
+        .with(GoodThyme {})
+        .with(CakeIngredient {
+            adjective: "LEGENDARY".to_string(),
+            super_adjective: "MYTHICAL".to_string(),
+            overall_points: 12,
+            moist_points: 3,
+            sweet_points: 3,
+            style_points: 10,
+            hot_points: 0,
+            mold_points: 0,
+            edible_points: 3,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
 pub fn mushroom(ecs: &mut World, x: i32, y: i32, name: String, low_hp: i32, high_hp: i32) {
     let hp = ecs
         .get_mut::<RandomNumberGenerator>()
         .expect("rng should always be available")
         .roll_dice(1, high_hp - low_hp)
         + low_hp;
-    ecs.create_entity()
+    let m = ecs
+        .create_entity()
         .with(Position { x, y })
         .with(Renderable {
             glyph: rltk::to_cp437('♣'),
@@ -467,7 +559,9 @@ pub fn mushroom(ecs: &mut World, x: i32, y: i32, name: String, low_hp: i32, high
         .with(Name {
             name: name.clone() + " MUSHROOM",
         })
+        .with(Perishable::new(&(name.clone() + " MUSHROOM"), 800))
         .with(Item {})
+        .with(Worth { value: 2 })
         .with(ProvidesFood {})
         .with(Consumable {})
         .with(ProvidesHealing { heal_amount: hp })
@@ -484,6 +578,15 @@ pub fn mushroom(ecs: &mut World, x: i32, y: i32, name: String, low_hp: i32, high
         })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
+    if name == "MAGIC" {
+        let _ = ecs.write_storage::<InflictsStatus>().insert(
+            m,
+            InflictsStatus {
+                kind: StatusKind::High,
+                turns: 20,
+            },
+        );
+    }
 }
 
 pub fn friendly_crow(ecs: &mut World, x: i32, y: i32) {
@@ -499,6 +602,7 @@ pub fn friendly_crow(ecs: &mut World, x: i32, y: i32) {
             name: "FRIENDLY CROW".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 6 })
         .with(Rare {})
         .with(MagicMapper {})
         .with(Consumable {})
@@ -530,6 +634,7 @@ pub fn friendly_eagle(ecs: &mut World, x: i32, y: i32) {
             name: "FRIENDLY EAGLE".to_string(),
         })
         .with(Item {})
+        .with(Worth { value: 10 })
         .with(Rare {})
         .with(TeleportsPlayer { level: 1 })
         .with(Consumable {})
@@ -547,3 +652,382 @@ pub fn friendly_eagle(ecs: &mut World, x: i32, y: i32) {
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 }
+
+fn pastry<S: ToString>(
+    ecs: &mut World,
+    x: i32,
+    y: i32,
+    fg: rltk::RGB,
+    name: S,
+    worth: i32,
+    adjective: S,
+) -> EntityBuilder<'_> {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('∩'),
+            fg,
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: name.to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: worth })
+        .with(Rare {})
+        .with(Consumable {})
+        .with(CakeIngredient {
+            adjective: adjective.to_string(),
+            super_adjective: "PSYCHEDELIC".to_string(),
+            overall_points: 2,
+            moist_points: 1,
+            sweet_points: 2,
+            style_points: 2,
+            hot_points: 0,
+            mold_points: 0,
+            edible_points: 1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+pub fn mossy_muffin(ecs: &mut World, x: i32, y: i32) {
+    pastry(
+        ecs,
+        x,
+        y,
+        RGB::from_hex("#70a050").expect("hardcoded"),
+        "MOSSY MUFFIN",
+        6,
+        "MOSSY",
+    )
+    .with(ProvidesFood {})
+    .with(ProvidesHealing { heal_amount: 12 })
+    .build();
+}
+
+pub fn seeing_scone(ecs: &mut World, x: i32, y: i32) {
+    pastry(
+        ecs,
+        x,
+        y,
+        RGB::from_hex("#e0c070").expect("hardcoded"),
+        "SEEING SCONE",
+        8,
+        "ALL-SEEING",
+    )
+    .with(ProvidesFood {})
+    .with(MagicMapper {})
+    .build();
+}
+
+pub fn wobbly_eclair(ecs: &mut World, x: i32, y: i32) {
+    pastry(
+        ecs,
+        x,
+        y,
+        RGB::from_hex("#a070c0").expect("hardcoded"),
+        "WOBBLY ECLAIR",
+        6,
+        "WOBBLY",
+    )
+    .with(Ranged { range: 6 })
+    .with(InflictsStatus {
+        kind: StatusKind::Confused,
+        turns: 6,
+    })
+    .build();
+}
+
+pub fn starry_strudel(ecs: &mut World, x: i32, y: i32) {
+    pastry(
+        ecs,
+        x,
+        y,
+        RGB::from_hex("#80a0e0").expect("hardcoded"),
+        "STARRY STRUDEL",
+        14,
+        "STARRY",
+    )
+    .with(InflictsStatus {
+        kind: StatusKind::Glowing,
+        turns: 30,
+    })
+    .with(ProvidesFood {})
+    .with(ProvidesHealing { heal_amount: 30 })
+    .build();
+}
+
+pub fn bucket(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('u'),
+            fg: RGB::from_hex("#a08060").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "BUCKET".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { weight: 3 })
+        .with(Worth { value: 5 })
+        .with(HoldsWater {
+            sips: 0,
+            capacity: 3,
+            contaminated: false,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn purifying_moss(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('"'),
+            fg: RGB::from_hex("#60b0b0").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 3,
+        })
+        .with(Name {
+            name: "PURIFYING MOSS".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 3 })
+        .with(Consumable {})
+        .with(PurifiesWater {})
+        .with(CakeIngredient {
+            adjective: "SPONGY".to_string(),
+            super_adjective: "PRISTINE".to_string(),
+            overall_points: 1,
+            moist_points: 2,
+            sweet_points: 0,
+            style_points: 0,
+            hot_points: 0,
+            mold_points: -2,
+            edible_points: 1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn torch(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('í'),
+            fg: RGB::from_hex("#f0a040").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "TORCH".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { weight: 2 })
+        .with(Worth { value: 4 })
+        .with(LightSource { radius: 6 })
+        .with(Ranged { range: 2 })
+        .with(Ignites {})
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn woolly_scarf(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('§'),
+            fg: RGB::from_hex("#c06060").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "WOOLLY SCARF".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 6 })
+        .with(Equippable {
+            slot: EquipmentSlot::Shield,
+        })
+        .with(Warm {})
+        .with(CakeIngredient {
+            adjective: "FUZZY".to_string(),
+            super_adjective: "KNITTED".to_string(),
+            overall_points: 0,
+            moist_points: -1,
+            sweet_points: 0,
+            style_points: 3,
+            hot_points: 1,
+            mold_points: 0,
+            edible_points: -2,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn basket(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('∩'),
+            fg: RGB::from_hex("#c0a060").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "BASKET".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 5 })
+        .with(SlowsSpoilage { factor: 2 })
+        .with(ForagingGear {
+            tool: ForagingTool::Basket,
+            quality: 1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn cool_pouch(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('δ'),
+            fg: RGB::from_hex("#80c0e0").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "COOL-POUCH".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 9 })
+        .with(Rare {})
+        .with(SlowsSpoilage { factor: 3 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn smoker(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('Ω'),
+            fg: RGB::from_hex("#a0a0a0").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "SMOKER".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 6 })
+        .with(ForagingGear {
+            tool: ForagingTool::Smoker,
+            quality: 1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn wild_ginger(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('τ'),
+            fg: RGB::from_hex("#d0b070").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 3,
+        })
+        .with(Name {
+            name: "WILD GINGER".to_string(),
+        })
+        .with(Perishable::new("WILD GINGER", 900))
+        .with(Item {})
+        .with(Worth { value: 3 })
+        .with(ProvidesHealing { heal_amount: 1 })
+        .with(ProvidesFood {})
+        .with(Consumable {})
+        .with(InflictsStatus {
+            kind: StatusKind::Hasted,
+            turns: 10,
+        })
+        .with(CakeIngredient {
+            adjective: "ZINGY".to_string(),
+            super_adjective: "FIERY".to_string(),
+            overall_points: 1,
+            moist_points: 0,
+            sweet_points: 0,
+            style_points: 1,
+            hot_points: 3,
+            mold_points: 0,
+            edible_points: 1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn honeycomb(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('#'),
+            fg: RGB::from_hex("#f0c040").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 3,
+        })
+        .with(Name {
+            name: "HONEYCOMB".to_string(),
+        })
+        .with(Item {})
+        .with(Worth { value: 5 })
+        .with(ProvidesHealing { heal_amount: 3 })
+        .with(ProvidesFood {})
+        .with(Consumable {})
+        .with(CakeIngredient {
+            adjective: "HONEYED".to_string(),
+            super_adjective: "GOLDEN".to_string(),
+            overall_points: 2,
+            moist_points: 1,
+            sweet_points: 3,
+            style_points: 1,
+            hot_points: 0,
+            mold_points: 0,
+            edible_points: 1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn hatchet(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('Γ'),
+            fg: RGB::from_hex("#b0a090").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "HATCHET".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { weight: 3 })
+        .with(Worth { value: 10 })
+        .with(Equippable {
+            slot: EquipmentSlot::Melee,
+        })
+        .with(MeleeWeapon {
+            n_dice: 1,
+            die_type: 4,
+            bonus: 1,
+            hit_bonus: 0,
+            traits: Vec::new(),
+        })
+        .with(CutsTrees { power: 2 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
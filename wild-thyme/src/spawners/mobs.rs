@@ -1,11 +1,12 @@
 use crate::components::*;
+use crate::map::{Map, TileType};
 use crate::spawners::items::*;
-use rltk::RGB;
+use rltk::{RandomNumberGenerator, RGB};
 use specs::prelude::*;
 use specs::saveload::{MarkedBuilder, SimpleMarker};
 
 pub fn mosquito(ecs: &mut World, x: i32, y: i32) {
-    monster(
+    let m = monster(
         ecs,
         x,
         y,
@@ -16,9 +17,12 @@ pub fn mosquito(ecs: &mut World, x: i32, y: i32) {
         1,
         4,
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(m, Initiative::new(150));
 }
-pub fn spider(ecs: &mut World, x: i32, y: i32) {
-    monster(
+pub fn spider(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let m = monster(
         ecs,
         x,
         y,
@@ -29,9 +33,18 @@ pub fn spider(ecs: &mut World, x: i32, y: i32) {
         1,
         4,
     );
+    let _ = ecs.write_storage::<Predator>().insert(m, Predator {});
+    let _ = ecs.write_storage::<InflictsStatus>().insert(
+        m,
+        InflictsStatus {
+            kind: StatusKind::Poisoned,
+            turns: 4,
+        },
+    );
+    m
 }
-pub fn ghost(ecs: &mut World, x: i32, y: i32) {
-    monster(
+pub fn ghost(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let m = monster(
         ecs,
         x,
         y,
@@ -42,6 +55,19 @@ pub fn ghost(ecs: &mut World, x: i32, y: i32) {
         2,
         4,
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(m, Initiative::new(80));
+    m
+}
+// the ones that come out after dark lose interest again come morning
+pub fn night_spider(ecs: &mut World, x: i32, y: i32) {
+    let m = spider(ecs, x, y);
+    let _ = ecs.write_storage::<Nocturnal>().insert(m, Nocturnal {});
+}
+pub fn night_ghost(ecs: &mut World, x: i32, y: i32) {
+    let m = ghost(ecs, x, y);
+    let _ = ecs.write_storage::<Nocturnal>().insert(m, Nocturnal {});
 }
 pub fn sparrow(ecs: &mut World, x: i32, y: i32) {
     let m = monster(
@@ -55,6 +81,10 @@ pub fn sparrow(ecs: &mut World, x: i32, y: i32) {
         1,
         3,
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(m, Initiative::new(130));
+    let _ = ecs.write_storage::<Scavenger>().insert(m, Scavenger {});
     loot_egg(ecs, m);
 }
 pub fn ostrich(ecs: &mut World, x: i32, y: i32) {
@@ -69,8 +99,122 @@ pub fn ostrich(ecs: &mut World, x: i32, y: i32) {
         1,
         4,
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(m, Initiative::new(150));
+    // a charging ostrich sends you flying
+    let _ = ecs
+        .write_storage::<KnocksBack>()
+        .insert(m, KnocksBack { force: 2 });
+    let _ = ecs.write_storage::<Scavenger>().insert(m, Scavenger {});
     loot_egg(ecs, m);
 }
+pub fn wolf(ecs: &mut World, x: i32, y: i32) {
+    let m = monster(
+        ecs,
+        x,
+        y,
+        rltk::to_cp437('w'),
+        RGB::from_hex("#a0a0b0").expect("hardcoded"),
+        "WOLF",
+        10,
+        1,
+        5,
+    );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(m, Initiative::new(120));
+    let _ = ecs.write_storage::<Predator>().insert(m, Predator {});
+    let _ = ecs.write_storage::<PackHunter>().insert(m, PackHunter {});
+    let _ = ecs.write_storage::<Morale>().insert(m, Morale::new(10, 10));
+    loot_meat(ecs, m);
+}
+
+// a few wolves on whatever open ground is close by
+pub fn wolf_pack(ecs: &mut World, x: i32, y: i32) {
+    let mut dens = Vec::new();
+    {
+        let map = ecs.fetch::<Map>();
+        for dx in -2..=2 {
+            for dy in -2..=2 {
+                let (den_x, den_y) = (x + dx, y + dy);
+                if den_x < 1 || den_x > map.width - 2 || den_y < 1 || den_y > map.height - 2 {
+                    continue;
+                }
+                if map.tiles[map.xy_idx(den_x, den_y)] == TileType::Floor {
+                    dens.push((den_x, den_y));
+                }
+            }
+        }
+    }
+    let size = ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(1, 2)
+        + 1;
+    for _ in 0..size {
+        let den = {
+            let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+            match rng.random_slice_index(&dens) {
+                Some(i) => dens.remove(i),
+                None => return,
+            }
+        };
+        wolf(ecs, den.0, den.1);
+    }
+}
+
+pub fn the_wizard(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('☺'),
+            fg: RGB::from_hex("#c080f0").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 1,
+        })
+        .with(Viewshed {
+            visible_tiles: Vec::new(),
+            range: 9,
+            dirty: true,
+        })
+        .with(Monster {})
+        .with(Quips {
+            quips: vec![
+                "{NAME}! I'd know that face anywhere".to_string(),
+                "have we met? we've met. I'm sure of it".to_string(),
+                "am I a wizard? some days I think so".to_string(),
+                "the oven's still warm, {NAME}".to_string(),
+                "don't touch the mushrooms. well. not THOSE ones".to_string(),
+            ],
+            max_countdown: 10,
+            countdown: 0,
+        })
+        .with(Name {
+            name: "THE WIZARD".to_string(),
+        })
+        .with(Dialogue {
+            tree: "THE WIZARD".to_string(),
+            flags: Vec::new(),
+        })
+        .with(Persona {
+            persona: "a friendly, uncomfortably weird wizard baker living in a foggy tree stump, who guards the legendary ANCIENT THYME, isn't sure if he's really a wizard, and always gets people's names slightly wrong".to_string(),
+        })
+        .with(WizardBoss {
+            phase: BossPhase::Welcoming,
+        })
+        .with(BlocksTile {})
+        .with(CombatStats {
+            max_hp: 45,
+            hp: 45,
+            defense: 2,
+            power: 6,
+            dodge: 3,
+        })
+        .with(Initiative::new(110))
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
 pub fn dilophosaurus(ecs: &mut World, x: i32, y: i32) {
     let m = monster(
         ecs,
@@ -83,6 +227,22 @@ pub fn dilophosaurus(ecs: &mut World, x: i32, y: i32) {
         2,
         8,
     );
+    ecs.write_storage::<RangedAttack>()
+        .insert(
+            m,
+            RangedAttack {
+                range: 4,
+                damage: 2,
+                cooldown: 6,
+                cooldown_remaining: 0,
+                effect: RangedEffect::Damage,
+                glyph: rltk::to_cp437('~'),
+                color: RGB::from_hex("#a0e040").expect("hardcoded"),
+            },
+        )
+        .expect("should be able to give the dilophosaurus its spit");
+    let _ = ecs.write_storage::<Predator>().insert(m, Predator {});
+    let _ = ecs.write_storage::<Morale>().insert(m, Morale::new(12, 8));
     loot_egg(ecs, m);
 }
 
@@ -121,7 +281,11 @@ fn monster<S: ToString>(
             hp,
             defense,
             power,
+            dodge: 1,
         })
+        .with(Morale::new(8, hp))
+        .with(AiMemory::new(x, y))
+        .with(Initiative::new(100))
         .marked::<SimpleMarker<SerializeMe>>()
         .build()
 }
@@ -144,6 +308,7 @@ pub fn rey(ecs: &mut World, x: i32, y: i32) {
         .with(Quips {
             quips: vec![
                 "I'll zap you!".to_string(),
+                "hold still..".to_string(),
                 "one of these days...".to_string(),
             ],
             max_countdown: 10,
@@ -152,12 +317,22 @@ pub fn rey(ecs: &mut World, x: i32, y: i32) {
         .with(Name {
             name: "REY".to_string(),
         })
+        .with(RangedAttack {
+            range: 6,
+            damage: 0,
+            cooldown: 8,
+            cooldown_remaining: 4,
+            effect: RangedEffect::KnockItemLoose,
+            glyph: rltk::to_cp437('*'),
+            color: RGB::from_hex("#f0f060").expect("hardcoded"),
+        })
         .with(BlocksTile {})
         .with(CombatStats {
             max_hp: 10,
             hp: 10,
             defense: 4,
             power: 2,
+            dodge: 2,
         })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
@@ -192,12 +367,21 @@ pub fn pep(ecs: &mut World, x: i32, y: i32) {
         .with(Name {
             name: "PEPPERMINT WHOPPER MCGILLICUDY III".to_string(),
         })
+        .with(Vendor {
+            stock_table: "BLADES".to_string(),
+            stock: Vec::new(),
+            restocked_depth: 0,
+        })
+        .with(Persona {
+            persona: "a fast-talking rogue blade vendor who loves pointy things and burgers, and spits a little when excited".to_string(),
+        })
         .with(BlocksTile {})
         .with(CombatStats {
             max_hp: 10,
             hp: 10,
             defense: 4,
             power: 2,
+            dodge: 2,
         })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
@@ -237,7 +421,10 @@ fn creature<S: ToString>(
             hp: 8,
             defense: 0,
             power: 1,
+            dodge: 2,
         })
+        .with(Morale::new(3, 8))
+        .with(Initiative::new(100))
         .marked::<SimpleMarker<SerializeMe>>()
         .build()
 }
@@ -251,6 +438,9 @@ pub fn deer(ecs: &mut World, x: i32, y: i32) {
         RGB::named(rltk::SADDLE_BROWN),
         "DEER",
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(c, Initiative::new(120));
     loot_meat(ecs, c);
 }
 
@@ -263,6 +453,9 @@ pub fn squirrel(ecs: &mut World, x: i32, y: i32) {
         RGB::named(rltk::SADDLE_BROWN),
         "SQUIRREL",
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(c, Initiative::new(130));
     loot_meat(ecs, c);
 }
 
@@ -287,9 +480,26 @@ pub fn butterfly(ecs: &mut World, x: i32, y: i32) {
         RGB::named(rltk::LAVENDER),
         "BUTTERFLY",
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(c, Initiative::new(120));
     loot_meat(ecs, c);
 }
 
+pub fn firefly(ecs: &mut World, x: i32, y: i32) {
+    let c = creature(
+        ecs,
+        x,
+        y,
+        rltk::to_cp437('·'),
+        RGB::from_hex("#f0f080").expect("hardcoded"),
+        "FIREFLY",
+    );
+    let _ = ecs
+        .write_storage::<LightSource>()
+        .insert(c, LightSource { radius: 3 });
+}
+
 pub fn goat(ecs: &mut World, x: i32, y: i32) {
     let c = creature(
         ecs,
@@ -299,6 +509,9 @@ pub fn goat(ecs: &mut World, x: i32, y: i32) {
         RGB::from_hex("#888888").expect("hardcoded"),
         "GOAT",
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(c, Initiative::new(80));
     loot_milk(ecs, c);
 }
 
@@ -311,5 +524,8 @@ pub fn cow(ecs: &mut World, x: i32, y: i32) {
         RGB::from_hex("#888888").expect("hardcoded"),
         "COW",
     );
+    let _ = ecs
+        .write_storage::<Initiative>()
+        .insert(c, Initiative::new(60));
     loot_milk(ecs, c);
 }
@@ -14,6 +14,8 @@ pub mod mobs;
 use mobs::*;
 mod traps;
 use traps::*;
+mod forage;
+use forage::*;
 
 const MAX_MONSTERS: i32 = 4;
 
@@ -68,25 +70,49 @@ pub fn spawn_specific_on_point(ecs: &mut World, point: (i32, i32), spawnable: &S
         "HEALING HERBS" => healing_herbs(ecs, x, y),
         "GOODBERRY" => goodberry(ecs, x, y),
         "GOOD THYME" => thyme(ecs, x, y),
+        "ANCIENT THYME" => ancient_thyme(ecs, x, y),
         "WEIRD CONFUSING POWDER" => confusion_scroll(ecs, x, y),
         "SPARKLING POWDER" => sparkling_powder(ecs, x, y),
 
+        "MOSSY MUFFIN" => mossy_muffin(ecs, x, y),
+        "SEEING SCONE" => seeing_scone(ecs, x, y),
+        "WOBBLY ECLAIR" => wobbly_eclair(ecs, x, y),
+        "STARRY STRUDEL" => starry_strudel(ecs, x, y),
+
         "BEAR TRAP" => bear_trap(ecs, x, y),
         "PITFALL" => pitfall(ecs, x, y),
+        "MUD PATCH" => mud_patch(ecs, x, y),
 
         "DART GUN" => dart_gun(ecs, x, y),
         "POINTY STICK" => pointy_stick(ecs, x, y),
+        "CAKE KNIFE" => cake_knife(ecs, x, y),
+        "HATCHET" => hatchet(ecs, x, y),
         "BARK ARMOR" => bark_armor(ecs, x, y),
-        "BUCKET" => {} // hold liquids, splash liquids
-        "TORCH" => {}  // provide light, fire
+        "WOOLLY SCARF" => woolly_scarf(ecs, x, y),
+        "BASKET" => basket(ecs, x, y),
+        "COOL-POUCH" => cool_pouch(ecs, x, y),
+        "BUCKET" => bucket(ecs, x, y),
+        "PURIFYING MOSS" => purifying_moss(ecs, x, y),
+        "TORCH" => torch(ecs, x, y),
 
         "ROCK" => rock(ecs, x, y),
 
         "PUFFER MUSHROOM" => mushroom(ecs, x, y, "PUFFER".to_string(), 1, 5),
-        "MYSTERIOUS MUSHROOM" => mushroom(ecs, x, y, "MAGIC".to_string(), -15, 40),
+        "MYSTERIOUS MUSHROOM" | "MAGIC MUSHROOM" => {
+            mushroom(ecs, x, y, "MAGIC".to_string(), -15, 40)
+        }
         "MOREL MUSHROOM" => mushroom(ecs, x, y, "MOREL".to_string(), 10, 15),
 
         "BERRY BUSH" => berry_bush(ecs, x, y),
+        "BERRY SHRUB" => berry_shrub(ecs, x, y),
+        "ROOT PATCH" => root_patch(ecs, x, y),
+        "BEEHIVE" => beehive(ecs, x, y),
+        "MUSHROOM RING" => mushroom_ring(ecs, x, y),
+        "NESTING TREE" => nesting_tree(ecs, x, y),
+        "SMOKER" => smoker(ecs, x, y),
+        "WILD GINGER" => wild_ginger(ecs, x, y),
+        "HONEYCOMB" => honeycomb(ecs, x, y),
+        "EGG" => pos_egg(ecs, x, y),
 
         "BIRD NEST" => bird_nest(ecs, x, y),
         "SPARROW" => sparrow(ecs, x, y),
@@ -94,19 +120,29 @@ pub fn spawn_specific_on_point(ecs: &mut World, point: (i32, i32), spawnable: &S
         "OSTRICH" => ostrich(ecs, x, y),
         "DINOSAUR NEST" => dino_nest(ecs, x, y),
         "DILOPHOSAURUS" => dilophosaurus(ecs, x, y),
+        "WOLF" => wolf(ecs, x, y),
+        "WOLF PACK" => wolf_pack(ecs, x, y),
+        "THE WIZARD" => the_wizard(ecs, x, y),
 
         "DEER" => deer(ecs, x, y),
         "SQUIRREL" => squirrel(ecs, x, y),
         "FROG" => frog(ecs, x, y),
         "BUTTERFLY" => butterfly(ecs, x, y),
         "MOSQUITO" => mosquito(ecs, x, y),
-        "SPIDER" => spider(ecs, x, y),
+        "SPIDER" => {
+            spider(ecs, x, y);
+        }
+        "NIGHT SPIDER" => night_spider(ecs, x, y),
         "GOAT" => goat(ecs, x, y),
         "COW" => cow(ecs, x, y),
-        "GHOST" => ghost(ecs, x, y),
+        "GHOST" => {
+            ghost(ecs, x, y);
+        }
+        "NIGHT GHOST" => night_ghost(ecs, x, y),
         "REY" => rey(ecs, x, y),
         "PEPPERMINT WHOPPER" => pep(ecs, x, y),
-        "FIREFLY" => {} // provides light
+        "FIREFLY" => firefly(ecs, x, y),
+        "FOG" => fog(ecs, x, y, crate::fog::FOG_SPREAD_LIFETIME),
 
         _ => {}
     }
@@ -145,6 +181,23 @@ pub fn spawn_treeportal(ecs: &mut World, room: &Rect) {
         .build();
 }
 
+pub fn fog(ecs: &mut World, x: i32, y: i32, lifetime_rounds: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('▒'),
+            fg: RGB::from_hex("#8a7fa0").expect("hardcoded"),
+            bg: RGB::from_hex("#1a1824").expect("hardcoded"),
+            render_order: 1,
+        })
+        .with(Name {
+            name: "MYSTERIOUS FOG".to_string(),
+        })
+        .with(Fog { lifetime_rounds })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
 pub fn player(ecs: &mut World, player_x: i32, player_y: i32) -> Entity {
     ecs.create_entity()
         .with(Position {
@@ -171,19 +224,30 @@ pub fn player(ecs: &mut World, player_x: i32, player_y: i32) -> Entity {
             hp: 30,
             defense: 2,
             power: 5,
+            dodge: 2,
         })
         .with(HungerClock {
             state: crate::components::HungerState::Full,
             duration: 20,
         })
+        .with(ThirstClock {
+            state: ThirstState::Quenched,
+            duration: 30,
+        })
         .with(Backpack {
             capacity: 10,
             items: 0,
         })
+        .with(Initiative::new(100))
+        .with(KnownRecipes {
+            recipes: Vec::new(),
+        })
         .marked::<SimpleMarker<SerializeMe>>()
         .build()
 }
 
+// the forage spots and camping gear take their share from the rocks and small
+// critters, so the total stays close to what it was and nothing else gets rarer
 fn room_table(map_depth: i32) -> RandomTable {
     RandomTable::new()
         .add("FRIENDLY CROW", 2)
@@ -192,26 +256,46 @@ fn room_table(map_depth: i32) -> RandomTable {
         .add("GOODBERRY", 4)
         .add("SPARKLING POWDER", 1 + map_depth)
         .add("BERRY BUSH", 5)
+        .add("BERRY SHRUB", 2)
+        .add("ROOT PATCH", 2)
+        .add("BEEHIVE", 1)
+        .add("MUSHROOM RING", 1)
+        .add("NESTING TREE", 1)
+        .add("SMOKER", 1)
         .add("GOOD THYME", 1 + map_depth / 2)
         .add("WEIRD CONFUSING POWDER", 2 + map_depth)
         .add("BEAR TRAP", 3 + map_depth * 2)
         .add("PITFALL", 10)
+        .add("MUD PATCH", 3)
         .add("POINTY STICK", 3)
+        .add("HATCHET", 1)
         .add("BARK ARMOR", 3)
-        .add("ROCK", 15)
+        .add("WOOLLY SCARF", 1)
+        .add("BASKET", 1)
+        .add("ROCK", 10)
+        .add("BUCKET", 2)
+        .add("TORCH", 2)
+        .add("PURIFYING MOSS", 1 + map_depth / 3)
         .add("PUFFER MUSHROOM", 5 + map_depth * 2)
         .add("MAGIC MUSHROOM", 1 + map_depth * 2)
         .add("MOREL MUSHROOM", 3 + map_depth * 2)
         .add("DEER", 10)
-        .add("FROG", 15)
-        .add("SPARROW", 15)
-        .add("SQUIRREL", 15)
+        .add("FROG", 11)
+        .add("SPARROW", 11)
+        .add("SQUIRREL", 11)
         .add("COW", 10 + map_depth)
         .add("GOAT", 10 + map_depth)
         .add("SPIDER", 2 + map_depth * 2)
         .add("OSTRICH", 2 + map_depth * 2)
         .add("GHOST", map_depth)
         .add("DILOPHOSAURUS", map_depth)
+        .add(
+            "WOLF PACK",
+            match map_depth {
+                6..=10 => map_depth - 2,
+                _ => 0,
+            },
+        )
         .add("REY", map_depth / 2)
         .add("PEPPERMINT WHOPPER", map_depth / 2)
 }
@@ -231,7 +315,7 @@ pub fn npc<S: ToString>(
     fg: rltk::RGB,
     name: S,
     quips: Vec<String>,
-) {
+) -> Entity {
     ecs.create_entity()
         .with(Position { x, y })
         .with(Monster {})
@@ -256,5 +340,41 @@ pub fn npc<S: ToString>(
             countdown: 0,
         })
         .marked::<SimpleMarker<SerializeMe>>()
-        .build();
+        .build()
+}
+
+pub fn give_vendor(ecs: &mut World, npc: Entity, stock_table: &str) {
+    ecs.write_storage::<Vendor>()
+        .insert(
+            npc,
+            Vendor {
+                stock_table: stock_table.to_string(),
+                stock: Vec::new(),
+                restocked_depth: 0,
+            },
+        )
+        .expect("should be able to make npc a vendor");
+}
+
+pub fn give_persona(ecs: &mut World, npc: Entity, persona: &str) {
+    ecs.write_storage::<Persona>()
+        .insert(
+            npc,
+            Persona {
+                persona: persona.to_string(),
+            },
+        )
+        .expect("should be able to give npc a persona");
+}
+
+pub fn give_dialogue(ecs: &mut World, npc: Entity, tree: &str) {
+    ecs.write_storage::<Dialogue>()
+        .insert(
+            npc,
+            Dialogue {
+                tree: tree.to_string(),
+                flags: Vec::new(),
+            },
+        )
+        .expect("should be able to give npc dialogue");
 }
@@ -20,6 +20,10 @@ pub fn bear_trap(ecs: &mut World, x: i32, y: i32) {
             verb: "springs".to_string(),
         })
         .with(InflictsDamage { damage: 6 })
+        .with(InflictsStatus {
+            kind: StatusKind::Slowed,
+            turns: 8,
+        })
         .with(SingleActivation {})
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
@@ -41,13 +45,39 @@ pub fn pitfall(ecs: &mut World, x: i32, y: i32) {
         .with(EntryTrigger {
             verb: "opens up".to_string(),
         })
-        .with(Confusion { turns: 4 })
+        .with(InflictsStatus {
+            kind: StatusKind::Confused,
+            turns: 4,
+        })
         .with(InflictsDamage { damage: 1 })
         .with(SingleActivation {})
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 }
 
+pub fn mud_patch(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: rltk::to_cp437('~'),
+            fg: RGB::from_hex("#705030").expect("hardcoded"),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "MUD PATCH".to_string(),
+        })
+        .with(EntryTrigger {
+            verb: "squelches".to_string(),
+        })
+        .with(InflictsStatus {
+            kind: StatusKind::Slowed,
+            turns: 4,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
 pub fn berry_bush(ecs: &mut World, x: i32, y: i32) {
     ecs.create_entity()
         .with(Position { x, y })
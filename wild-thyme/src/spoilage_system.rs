@@ -0,0 +1,128 @@
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        CakeIngredient, InBackpack, Name, Perishable, ProvidesHealing, SlowsSpoilage, SpoilState,
+    },
+    day_night_system::WorldClock,
+    gamelog::{GameLog, LogEntry},
+    stats::LevelStats,
+    RunState,
+};
+
+pub struct SpoilageSystem {}
+
+impl<'a> System<'a> for SpoilageSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, WorldClock>,
+        WriteStorage<'a, Perishable>,
+        ReadStorage<'a, SlowsSpoilage>,
+        ReadStorage<'a, InBackpack>,
+        WriteStorage<'a, Name>,
+        WriteStorage<'a, CakeIngredient>,
+        WriteStorage<'a, ProvidesHealing>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            runstate,
+            clock,
+            mut perishables,
+            slows_spoilage,
+            backpack_items,
+            mut names,
+            mut ingredients,
+            mut healing,
+            mut log,
+            mut level_stats,
+        ) = data;
+
+        if *runstate != RunState::CorePlayerTurn {
+            return;
+        }
+
+        // the best container in the backpack keeps everything fresher
+        let factor = (&slows_spoilage, &backpack_items)
+            .join()
+            .filter(|(_, pack)| pack.owner == *player_entity)
+            .map(|(slows, _)| slows.factor)
+            .max()
+            .unwrap_or(1);
+        if clock.turn % factor != 0 {
+            return;
+        }
+        let player_name = names
+            .get(*player_entity)
+            .map(|name| name.name.clone())
+            .unwrap_or_default();
+
+        for (entity, perishable, pack) in (&entities, &mut perishables, &backpack_items).join() {
+            if pack.owner != *player_entity || perishable.freshness < 1 {
+                continue;
+            }
+            perishable.freshness -= 1;
+            let state = if perishable.freshness < 1 {
+                SpoilState::Rotten
+            } else if perishable.freshness * 2 < perishable.max_freshness {
+                SpoilState::Stale
+            } else {
+                SpoilState::Fresh
+            };
+            if state == perishable.state {
+                continue;
+            }
+            perishable.state = state;
+
+            if let Some(name) = names.get_mut(entity) {
+                match state {
+                    SpoilState::Fresh => name.name = perishable.base_name.clone(),
+                    SpoilState::Stale => {
+                        name.name = format!("STALE {}", perishable.base_name);
+                        log.log(LogEntry::Notification {
+                            notification: format!(
+                                "{}'s {} has gone STALE.",
+                                player_name, perishable.base_name
+                            ),
+                        });
+                    }
+                    SpoilState::Rotten => {
+                        name.name = format!("ROTTEN {}", perishable.base_name);
+                        log.log(LogEntry::Alert {
+                            alert: format!(
+                                "{}'s {} is ROTTEN! ew..",
+                                player_name, perishable.base_name
+                            ),
+                        });
+                    }
+                }
+            }
+            if let Some(ingredient) = ingredients.get_mut(entity) {
+                match state {
+                    SpoilState::Fresh => {}
+                    SpoilState::Stale => {
+                        ingredient.mold_points += 1;
+                        ingredient.edible_points -= 1;
+                    }
+                    SpoilState::Rotten => {
+                        ingredient.mold_points += 2;
+                        ingredient.edible_points -= 2;
+                        ingredient.adjective = "ROTTEN".to_string();
+                    }
+                }
+            }
+            if state == SpoilState::Rotten {
+                if let Some(healing) = healing.get_mut(entity) {
+                    healing.heal_amount = i32::min(healing.heal_amount, -2);
+                }
+                level_stats.ingredients_spoiled += 1;
+            }
+        }
+    }
+}
@@ -3,9 +3,14 @@ use std::cmp::{max, min};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::weather::Weather;
+
+pub const MAX_PLAYER_NAME_LENGTH: usize = 10;
+
 #[derive(PartialEq, Clone, Serialize, Deserialize)]
 pub struct OverallStats {
     pub name: String,
+    pub run_seed: u64,
     pub deepest_level: i32,
     pub most_items_held: i32,
     pub thyme_eaten: i32,
@@ -20,10 +25,26 @@ pub struct OverallStats {
     pub hunger_steps: i32,
     pub starving_steps: i32,
     pub well_fed_steps: i32,
+    pub water_drunk: i32,
+    pub murky_water_drunk: i32,
+    pub thirsty_steps: i32,
+    pub parched_steps: i32,
+    pub puddles_formed: i32,
+    pub cold_steps: i32,
+    pub ingredients_spoiled: i32,
+    pub ingredients_foraged: i32,
+    pub trees_cleared: i32,
+    pub fires_started: i32,
+    pub tiles_burned: i32,
+    pub items_crafted: i32,
+    pub recipes_discovered: i32,
+    pub chases_escaped: i32,
+    pub things_shoved: i32,
+    pub things_thrown: i32,
     pub cake: CakeStats,
 }
 
-#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Serialize, Deserialize)]
 #[wasm_bindgen]
 pub struct LevelStats {
     pub level: i32,
@@ -40,6 +61,24 @@ pub struct LevelStats {
     pub hunger_steps: i32,
     pub starving_steps: i32,
     pub well_fed_steps: i32,
+    pub water_drunk: i32,
+    pub murky_water_drunk: i32,
+    pub thirsty_steps: i32,
+    pub parched_steps: i32,
+    #[wasm_bindgen(getter_with_clone)]
+    pub weather: String,
+    pub puddles_formed: i32,
+    pub cold_steps: i32,
+    pub ingredients_spoiled: i32,
+    pub ingredients_foraged: i32,
+    pub trees_cleared: i32,
+    pub fires_started: i32,
+    pub tiles_burned: i32,
+    pub items_crafted: i32,
+    pub recipes_discovered: i32,
+    pub chases_escaped: i32,
+    pub things_shoved: i32,
+    pub things_thrown: i32,
 }
 
 impl LevelStats {
@@ -59,6 +98,23 @@ impl LevelStats {
             hunger_steps: 0,
             starving_steps: 0,
             well_fed_steps: 0,
+            water_drunk: 0,
+            murky_water_drunk: 0,
+            thirsty_steps: 0,
+            parched_steps: 0,
+            weather: Weather::Clear.name().to_string(),
+            puddles_formed: 0,
+            cold_steps: 0,
+            ingredients_spoiled: 0,
+            ingredients_foraged: 0,
+            trees_cleared: 0,
+            fires_started: 0,
+            tiles_burned: 0,
+            items_crafted: 0,
+            recipes_discovered: 0,
+            chases_escaped: 0,
+            things_shoved: 0,
+            things_thrown: 0,
         }
     }
     pub fn reset(&mut self, level: i32) {
@@ -74,6 +130,23 @@ impl LevelStats {
         self.hunger_steps = 0;
         self.starving_steps = 0;
         self.well_fed_steps = 0;
+        self.water_drunk = 0;
+        self.murky_water_drunk = 0;
+        self.thirsty_steps = 0;
+        self.parched_steps = 0;
+        self.weather = Weather::Clear.name().to_string();
+        self.puddles_formed = 0;
+        self.cold_steps = 0;
+        self.ingredients_spoiled = 0;
+        self.ingredients_foraged = 0;
+        self.trees_cleared = 0;
+        self.fires_started = 0;
+        self.tiles_burned = 0;
+        self.items_crafted = 0;
+        self.recipes_discovered = 0;
+        self.chases_escaped = 0;
+        self.things_shoved = 0;
+        self.things_thrown = 0;
     }
 }
 
@@ -92,7 +165,8 @@ pub struct CakeStats {
 impl OverallStats {
     pub fn new() -> OverallStats {
         OverallStats {
-            name: format!("YOU"),
+            name: String::new(),
+            run_seed: 0,
             deepest_level: 0,
             most_items_held: 0,
             thyme_eaten: 0,
@@ -107,6 +181,22 @@ impl OverallStats {
             hunger_steps: 0,
             starving_steps: 0,
             well_fed_steps: 0,
+            water_drunk: 0,
+            murky_water_drunk: 0,
+            thirsty_steps: 0,
+            parched_steps: 0,
+            puddles_formed: 0,
+            cold_steps: 0,
+            ingredients_spoiled: 0,
+            ingredients_foraged: 0,
+            trees_cleared: 0,
+            fires_started: 0,
+            tiles_burned: 0,
+            items_crafted: 0,
+            recipes_discovered: 0,
+            chases_escaped: 0,
+            things_shoved: 0,
+            things_thrown: 0,
             cake: CakeStats {
                 description: "".to_string(),
                 overall_points: 0,
@@ -120,7 +210,54 @@ impl OverallStats {
         }
     }
 
-    pub fn apply_level(&mut self, level_stats: LevelStats) {
+    pub fn set_name(&mut self, name: &str) -> bool {
+        match validate_player_name(name) {
+            Some(valid_name) => {
+                self.name = valid_name;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // lets data (like dialogue conditions) refer to stats by name
+    pub fn stat(&self, stat: &str) -> Option<i32> {
+        match stat {
+            "deepest_level" => Some(self.deepest_level),
+            "most_items_held" => Some(self.most_items_held),
+            "thyme_eaten" => Some(self.thyme_eaten),
+            "min_hp" => Some(self.min_hp),
+            "critters_killed" => Some(self.critters_killed),
+            "monsters_killed" => Some(self.monsters_killed),
+            "traps_triggered" => Some(self.traps_triggered),
+            "portals_taken" => Some(self.portals_taken),
+            "steps_taken" => Some(self.steps_taken),
+            "waits_taken" => Some(self.waits_taken),
+            "food_eaten" => Some(self.food_eaten),
+            "hunger_steps" => Some(self.hunger_steps),
+            "starving_steps" => Some(self.starving_steps),
+            "well_fed_steps" => Some(self.well_fed_steps),
+            "water_drunk" => Some(self.water_drunk),
+            "murky_water_drunk" => Some(self.murky_water_drunk),
+            "thirsty_steps" => Some(self.thirsty_steps),
+            "parched_steps" => Some(self.parched_steps),
+            "puddles_formed" => Some(self.puddles_formed),
+            "cold_steps" => Some(self.cold_steps),
+            "ingredients_spoiled" => Some(self.ingredients_spoiled),
+            "ingredients_foraged" => Some(self.ingredients_foraged),
+            "trees_cleared" => Some(self.trees_cleared),
+            "fires_started" => Some(self.fires_started),
+            "tiles_burned" => Some(self.tiles_burned),
+            "items_crafted" => Some(self.items_crafted),
+            "recipes_discovered" => Some(self.recipes_discovered),
+            "chases_escaped" => Some(self.chases_escaped),
+            "things_shoved" => Some(self.things_shoved),
+            "things_thrown" => Some(self.things_thrown),
+            _ => None,
+        }
+    }
+
+    pub fn apply_level(&mut self, level_stats: &LevelStats) {
         self.deepest_level = max(self.deepest_level, level_stats.level);
         self.most_items_held = max(self.most_items_held, level_stats.items_held);
         self.thyme_eaten += level_stats.thyme_eaten;
@@ -135,5 +272,36 @@ impl OverallStats {
         self.hunger_steps += level_stats.hunger_steps;
         self.starving_steps += level_stats.starving_steps;
         self.well_fed_steps += level_stats.well_fed_steps;
+        self.water_drunk += level_stats.water_drunk;
+        self.murky_water_drunk += level_stats.murky_water_drunk;
+        self.thirsty_steps += level_stats.thirsty_steps;
+        self.parched_steps += level_stats.parched_steps;
+        self.puddles_formed += level_stats.puddles_formed;
+        self.cold_steps += level_stats.cold_steps;
+        self.ingredients_spoiled += level_stats.ingredients_spoiled;
+        self.ingredients_foraged += level_stats.ingredients_foraged;
+        self.trees_cleared += level_stats.trees_cleared;
+        self.fires_started += level_stats.fires_started;
+        self.tiles_burned += level_stats.tiles_burned;
+        self.items_crafted += level_stats.items_crafted;
+        self.recipes_discovered += level_stats.recipes_discovered;
+        self.chases_escaped += level_stats.chases_escaped;
+        self.things_shoved += level_stats.things_shoved;
+        self.things_thrown += level_stats.things_thrown;
+    }
+}
+
+// same rules as the web name box: 1-10 letters, numbers, spaces or underscores
+pub fn validate_player_name(name: &str) -> Option<String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > MAX_PLAYER_NAME_LENGTH {
+        return None;
+    }
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '_')
+    {
+        return None;
     }
+    Some(trimmed.to_uppercase())
 }
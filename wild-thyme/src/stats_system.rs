@@ -3,6 +3,7 @@ use std::cmp::min;
 use crate::{
     components::{
         CombatStats, EntityMoved, Equipped, HostileToPlayer, HungerClock, HungerState, InBackpack,
+        ThirstClock, ThirstState,
     },
     stats::LevelStats,
 };
@@ -20,6 +21,7 @@ impl<'a> System<'a> for StatsSystem {
         ReadStorage<'a, Equipped>,
         ReadStorage<'a, EntityMoved>,
         ReadStorage<'a, HungerClock>,
+        ReadStorage<'a, ThirstClock>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -32,6 +34,7 @@ impl<'a> System<'a> for StatsSystem {
             equipped,
             moved_entities,
             hunger_clocks,
+            thirst_clocks,
         ) = data;
         // current number of player-held items
         level_stats.items_held = equipped
@@ -79,5 +82,14 @@ impl<'a> System<'a> for StatsSystem {
             HungerState::Starving => level_stats.starving_steps += 1,
             _ => {}
         }
+        // water drunk this round handled imperatively in thirst system..
+        // level_stats.water_drunk += 0;
+        if let Some(thirst_clock) = thirst_clocks.get(*player_entity) {
+            match thirst_clock.state {
+                ThirstState::Thirsty => level_stats.thirsty_steps += 1,
+                ThirstState::Parched => level_stats.parched_steps += 1,
+                _ => {}
+            }
+        }
     }
 }
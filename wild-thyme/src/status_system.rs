@@ -0,0 +1,229 @@
+use rltk::{RandomNumberGenerator, RGB};
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        AreaOfEffect, CombatStats, InflictsStatus, Name, Position, StatusEffects, StatusKind,
+        SufferDamage, WantsToUseItem,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    particle_system::ParticleBuilder,
+    RunState,
+};
+
+const POISON_DAMAGE: i32 = 1;
+
+// ticks everyone's status effects down once per turn of their own
+pub struct StatusSystem {}
+
+impl<'a> System<'a> for StatusSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Map>,
+        WriteStorage<'a, StatusEffects>,
+        WriteStorage<'a, SufferDamage>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Name>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            runstate,
+            player_entity,
+            map,
+            mut statuses,
+            mut suffer_damage,
+            positions,
+            names,
+            mut particle_builder,
+            mut log,
+            mut rng,
+        ) = data;
+
+        let mut cured: Vec<Entity> = Vec::new();
+        for (entity, status, pos) in (&entities, &mut statuses, &positions).join() {
+            let is_player = entity == *player_entity;
+            let proceed = match *runstate {
+                RunState::CorePlayerTurn => is_player,
+                RunState::CoreMonsterTurn => !is_player,
+                _ => false,
+            };
+            if !proceed {
+                continue;
+            }
+
+            let seen = map.visible_tiles[map.xy_idx(pos.x, pos.y)];
+            for effect in status.effects.iter_mut() {
+                match effect.kind {
+                    StatusKind::Poisoned => {
+                        SufferDamage::new_damage(&mut suffer_damage, entity, POISON_DAMAGE);
+                        particle_builder.request(
+                            pos.x,
+                            pos.y,
+                            RGB::from_hex(effect.kind.color()).expect("hardcoded"),
+                            RGB::named(rltk::BLACK),
+                            rltk::to_cp437(effect.kind.glyph()),
+                            200.0,
+                        );
+                        if is_player {
+                            log.log(LogEntry::Alert {
+                                alert: format!("the POISON stings, {} damage!", POISON_DAMAGE),
+                            });
+                        }
+                    }
+                    StatusKind::High => {
+                        // the world goes all swirly
+                        let colors = ["#e070a0", "#70e0a0", "#70a0e0", "#e0e070"];
+                        let color = colors[rng.roll_dice(1, colors.len() as i32) as usize - 1];
+                        particle_builder.request(
+                            pos.x + rng.roll_dice(1, 3) - 2,
+                            pos.y + rng.roll_dice(1, 3) - 2,
+                            RGB::from_hex(color).expect("hardcoded"),
+                            RGB::named(rltk::BLACK),
+                            rltk::to_cp437('*'),
+                            200.0,
+                        );
+                    }
+                    _ => {}
+                }
+                effect.turns -= 1;
+                if effect.turns < 1 {
+                    if is_player {
+                        log.log(LogEntry::Notification {
+                            notification: format!(
+                                "{} is no longer {}.",
+                                names
+                                    .get(entity)
+                                    .expect("player should always have Name")
+                                    .name,
+                                effect.kind.name()
+                            ),
+                        });
+                    } else if seen {
+                        if let Some(name) = names.get(entity) {
+                            log.log(LogEntry::Notification {
+                                notification: format!(
+                                    "the {} is no longer {}.",
+                                    name.name,
+                                    effect.kind.name()
+                                ),
+                            });
+                        }
+                    }
+                }
+            }
+            status.effects.retain(|effect| effect.turns > 0);
+            if status.effects.is_empty() {
+                cured.push(entity);
+            }
+        }
+
+        for entity in cured {
+            statuses.remove(entity);
+        }
+    }
+}
+
+// items that pass on a status, sorted out before UseItemSystem gets to them
+pub struct StatusUseSystem {}
+
+impl<'a> System<'a> for StatusUseSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Map>,
+        ReadStorage<'a, WantsToUseItem>,
+        ReadStorage<'a, InflictsStatus>,
+        ReadStorage<'a, AreaOfEffect>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Position>,
+        WriteStorage<'a, StatusEffects>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, GameLog>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            map,
+            wants_use,
+            inflicts_status,
+            aoe,
+            combat_stats,
+            names,
+            positions,
+            mut statuses,
+            mut particle_builder,
+            mut log,
+        ) = data;
+
+        for (entity, used_item) in (&entities, &wants_use).join() {
+            let inflicts = match inflicts_status.get(used_item.item) {
+                Some(inflicts) => inflicts,
+                None => continue,
+            };
+            let mut targets: Vec<Entity> = Vec::new();
+            match used_item.target {
+                None => targets.push(entity),
+                Some(target) => {
+                    let radius = aoe.get(used_item.item).map_or(0, |area| area.radius);
+                    let mut tiles = match radius {
+                        0 => vec![target],
+                        _ => rltk::field_of_view(target, radius, &*map),
+                    };
+                    tiles.retain(|p| {
+                        p.x > 0 && p.x < map.width - 1 && p.y > 0 && p.y < map.height - 1
+                    });
+                    for tile in tiles.iter() {
+                        let idx = map.xy_idx(tile.x, tile.y);
+                        targets.extend(map.tile_content[idx].iter());
+                    }
+                }
+            }
+
+            for target in targets.iter().filter(|t| combat_stats.contains(**t)) {
+                StatusEffects::inflict(&mut statuses, *target, inflicts.kind, inflicts.turns);
+                if let Some(pos) = positions.get(*target) {
+                    particle_builder.request(
+                        pos.x,
+                        pos.y,
+                        RGB::from_hex(inflicts.kind.color()).expect("hardcoded"),
+                        RGB::named(rltk::BLACK),
+                        rltk::to_cp437(inflicts.kind.glyph()),
+                        200.0,
+                    );
+                }
+                if entity != *player_entity {
+                    continue;
+                }
+                let player_name = &names
+                    .get(*player_entity)
+                    .expect("player should always have Name")
+                    .name;
+                if *target == *player_entity {
+                    log.log(LogEntry::Alert {
+                        alert: format!("{} is {}!", player_name, inflicts.kind.name()),
+                    });
+                } else if let (Some(item_name), Some(mob_name)) =
+                    (names.get(used_item.item), names.get(*target))
+                {
+                    log.log(LogEntry::Action {
+                        subject: player_name.clone(),
+                        verb: format!("used {} on", item_name.name),
+                        object: format!("{}", mob_name.name),
+                        suffix: format!(", and it is {}!", inflicts.kind.name()),
+                    });
+                }
+            }
+        }
+    }
+}
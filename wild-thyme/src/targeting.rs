@@ -0,0 +1,109 @@
+use rltk::Point;
+use specs::prelude::*;
+
+use crate::{
+    components::{HostileToPlayer, Position, Viewshed},
+    map::Map,
+};
+
+// where the targeting cursor is, and who was aimed at last time
+pub struct TargetingCursor {
+    pub pos: Point,
+    pub last_target: Option<Entity>,
+    // only a mouse that has moved since targeting started takes over the cursor
+    pub mouse: Option<(i32, i32)>,
+}
+
+impl TargetingCursor {
+    pub fn new() -> TargetingCursor {
+        TargetingCursor {
+            pos: Point::new(0, 0),
+            last_target: None,
+            mouse: None,
+        }
+    }
+}
+
+pub fn in_range(ecs: &World, target: Point, range: i32) -> bool {
+    let player_entity = *ecs.fetch::<Entity>();
+    let player_pos = *ecs.fetch::<Point>();
+    let in_view = ecs
+        .read_storage::<Viewshed>()
+        .get(player_entity)
+        .map_or(false, |viewshed| viewshed.visible_tiles.contains(&target));
+    in_view && rltk::DistanceAlg::Pythagoras.distance2d(player_pos, target) <= range as f32
+}
+
+// visible hostiles in range, nearest first
+pub fn targets_in_range(ecs: &World, range: i32) -> Vec<(Entity, Point)> {
+    let player_pos = *ecs.fetch::<Point>();
+    let entities = ecs.entities();
+    let positions = ecs.read_storage::<Position>();
+    let hostiles = ecs.read_storage::<HostileToPlayer>();
+    let mut targets: Vec<(Entity, Point)> = (&entities, &positions, &hostiles)
+        .join()
+        .map(|(entity, pos, _)| (entity, Point::new(pos.x, pos.y)))
+        .filter(|(_, pos)| in_range(ecs, *pos, range))
+        .collect();
+    targets.sort_by(|a, b| {
+        let da = rltk::DistanceAlg::Pythagoras.distance2d(player_pos, a.1);
+        let db = rltk::DistanceAlg::Pythagoras.distance2d(player_pos, b.1);
+        da.partial_cmp(&db).expect("distances should compare")
+    });
+    targets
+}
+
+// puts the cursor back on the last target if it's still around, otherwise the nearest one
+pub fn start_targeting(ecs: &mut World, range: i32) {
+    let player_pos = *ecs.fetch::<Point>();
+    let targets = targets_in_range(ecs, range);
+    let mut cursor = ecs.fetch_mut::<TargetingCursor>();
+    let last = cursor
+        .last_target
+        .and_then(|last| targets.iter().find(|(entity, _)| *entity == last));
+    cursor.mouse = None;
+    cursor.pos = match last.or(targets.first()) {
+        Some((_, pos)) => *pos,
+        None => player_pos,
+    };
+}
+
+// moves the cursor to the next target after whatever it's on now
+pub fn cycle_target(ecs: &mut World, range: i32) {
+    let targets = targets_in_range(ecs, range);
+    if targets.is_empty() {
+        return;
+    }
+    let mut cursor = ecs.fetch_mut::<TargetingCursor>();
+    let next = match targets.iter().position(|(_, pos)| *pos == cursor.pos) {
+        Some(i) => (i + 1) % targets.len(),
+        None => 0,
+    };
+    cursor.pos = targets[next].1;
+}
+
+pub fn move_cursor(ecs: &mut World, dx: i32, dy: i32) {
+    let (width, height) = {
+        let map = ecs.fetch::<Map>();
+        (map.width, map.height)
+    };
+    let mut cursor = ecs.fetch_mut::<TargetingCursor>();
+    cursor.pos.x = i32::max(0, i32::min(width - 1, cursor.pos.x + dx));
+    cursor.pos.y = i32::max(0, i32::min(height - 1, cursor.pos.y + dy));
+}
+
+// remembers whoever is standing at the confirmed target
+pub fn remember_target(ecs: &mut World, target: Point) {
+    let target_entity = {
+        let entities = ecs.entities();
+        let positions = ecs.read_storage::<Position>();
+        let hostiles = ecs.read_storage::<HostileToPlayer>();
+        (&entities, &positions, &hostiles)
+            .join()
+            .find(|(_, pos, _)| pos.x == target.x && pos.y == target.y)
+            .map(|(entity, _, _)| entity)
+    };
+    if target_entity.is_some() {
+        ecs.fetch_mut::<TargetingCursor>().last_target = target_entity;
+    }
+}
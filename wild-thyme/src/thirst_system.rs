@@ -0,0 +1,256 @@
+use rltk::Point;
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        HoldsWater, InBackpack, Name, Position, PurifiesWater, SufferDamage, ThirstClock,
+        ThirstState, WantsToUseItem,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    particle_system::ParticleBuilder,
+    stats::{LevelStats, OverallStats},
+    window_fx, RunState,
+};
+
+pub const MURKY_WATER_DAMAGE: i32 = 2;
+
+pub struct ThirstSystem {}
+
+impl<'a> System<'a> for ThirstSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, ThirstClock>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        WriteStorage<'a, SufferDamage>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, Position>,
+        WriteExpect<'a, ParticleBuilder>,
+        ReadExpect<'a, OverallStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut thirst_clock,
+            player_entity,
+            runstate,
+            mut inflict_damage,
+            mut log,
+            positions,
+            mut particle_builder,
+            stats,
+        ) = data;
+
+        // only the player gets thirsty, critters know where the water is
+        if *runstate != RunState::CorePlayerTurn {
+            return;
+        }
+
+        for (entity, clock) in (&entities, &mut thirst_clock).join() {
+            if entity != *player_entity {
+                continue;
+            }
+            clock.duration -= 1;
+            if clock.duration >= 1 {
+                continue;
+            }
+            match clock.state {
+                ThirstState::Quenched => {
+                    clock.state = ThirstState::Normal;
+                    clock.duration = 250;
+                }
+                ThirstState::Normal => {
+                    clock.state = ThirstState::Thirsty;
+                    clock.duration = 150;
+                    log.log(LogEntry::Notification {
+                        notification: format!("{} is thirsty.", stats.name),
+                    });
+                }
+                ThirstState::Thirsty => {
+                    clock.state = ThirstState::Parched;
+                    log.log(LogEntry::Alert {
+                        alert: format!("{} is PARCHED!", stats.name),
+                    });
+                    if let Some(pos) = positions.get(entity) {
+                        particle_builder.request(
+                            pos.x,
+                            pos.y,
+                            rltk::RGB::named(rltk::TOMATO),
+                            rltk::RGB::named(rltk::BLACK),
+                            rltk::to_cp437('‼'),
+                            150.0,
+                        );
+                    }
+                    window_fx::nudge_effect();
+                }
+                ThirstState::Parched => {
+                    log.log(LogEntry::Alert {
+                        alert: format!("{}'s throat burns from the thirst D:", stats.name),
+                    });
+                    SufferDamage::new_damage(&mut inflict_damage, entity, 1);
+                }
+            }
+        }
+    }
+}
+
+// drinking from (or purifying) carried water, runs before the UseItemSystem
+pub struct WaterUseSystem {}
+
+impl<'a> System<'a> for WaterUseSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, WantsToUseItem>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, HoldsWater>,
+        ReadStorage<'a, PurifiesWater>,
+        ReadStorage<'a, InBackpack>,
+        WriteStorage<'a, ThirstClock>,
+        WriteStorage<'a, SufferDamage>,
+        ReadStorage<'a, Position>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, LevelStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            wants_use,
+            names,
+            mut holds_water,
+            purifies_water,
+            backpack_items,
+            mut thirst_clocks,
+            mut inflict_damage,
+            positions,
+            mut particle_builder,
+            mut level_stats,
+        ) = data;
+        let player_name = &names
+            .get(*player_entity)
+            .expect("player should always have Name")
+            .name;
+
+        for (entity, used_item) in (&entities, &wants_use).join() {
+            let item_name = names
+                .get(used_item.item)
+                .map(|name| name.name.clone())
+                .unwrap_or_default();
+
+            if let Some(water) = holds_water.get_mut(used_item.item) {
+                if water.sips < 1 {
+                    if entity == *player_entity {
+                        log.log(LogEntry::Notification {
+                            notification: format!(
+                                "the {} is empty. [SPACE] next to water to fill it.",
+                                item_name
+                            ),
+                        });
+                    }
+                    continue;
+                }
+                water.sips -= 1;
+                let contaminated = water.contaminated;
+                if water.sips < 1 {
+                    water.contaminated = false;
+                }
+                if let Some(clock) = thirst_clocks.get_mut(entity) {
+                    quench(clock);
+                }
+                if entity == *player_entity {
+                    level_stats.water_drunk += 1;
+                    log.log(LogEntry::Notification {
+                        notification: format!("{} drinks from the {}.", player_name, item_name),
+                    });
+                }
+                if contaminated {
+                    drank_murky_water(
+                        entity,
+                        (entity == *player_entity).then_some(player_name.as_str()),
+                        &mut log,
+                        &mut inflict_damage,
+                        &mut level_stats,
+                    );
+                }
+                if let Some(pos) = positions.get(entity) {
+                    particle_builder.request(
+                        pos.x,
+                        pos.y,
+                        rltk::RGB::from_hex("#70a0c0").expect("hardcoded"),
+                        rltk::RGB::named(rltk::BLACK),
+                        rltk::to_cp437('≈'),
+                        200.0,
+                    );
+                }
+            }
+
+            if purifies_water.get(used_item.item).is_some() {
+                let mut purified = 0;
+                for (water, pack) in (&mut holds_water, &backpack_items).join() {
+                    if pack.owner == entity && water.contaminated {
+                        water.contaminated = false;
+                        purified += 1;
+                    }
+                }
+                if entity == *player_entity {
+                    log.log(LogEntry::Notification {
+                        notification: match purified {
+                            0 => format!("the {} soaks up nothing but air..", item_name),
+                            _ => format!(
+                                "the {} leaves {}'s water crystal clear.",
+                                item_name, player_name
+                            ),
+                        },
+                    });
+                }
+            }
+        }
+    }
+}
+
+pub fn quench(clock: &mut ThirstClock) {
+    clock.state = ThirstState::Quenched;
+    clock.duration = 30;
+}
+
+pub fn drank_murky_water(
+    drinker: Entity,
+    player_name: Option<&str>,
+    log: &mut GameLog,
+    inflict_damage: &mut WriteStorage<SufferDamage>,
+    level_stats: &mut LevelStats,
+) {
+    SufferDamage::new_damage(inflict_damage, drinker, MURKY_WATER_DAMAGE);
+    if let Some(player_name) = player_name {
+        level_stats.murky_water_drunk += 1;
+        log.log(LogEntry::Alert {
+            alert: format!("{}'s tummy gurgles.. that water was MURKY!", player_name),
+        });
+    }
+}
+
+// only the town well and the druid's spring are safe to drink from
+pub fn water_is_murky(map: &Map) -> bool {
+    map.depth != 1 && map.depth % 5 != 0
+}
+
+pub fn next_to_water(map: &Map, pos: Point) -> bool {
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let (x, y) = (pos.x + dx, pos.y + dy);
+            if x < 0 || x > map.width - 1 || y < 0 || y > map.height - 1 {
+                continue;
+            }
+            if map.tiles[map.xy_idx(x, y)] == TileType::Water {
+                return true;
+            }
+        }
+    }
+    false
+}
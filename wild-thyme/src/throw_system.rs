@@ -0,0 +1,252 @@
+use rltk::Point;
+use specs::prelude::*;
+
+use crate::{
+    components::{
+        Backpack, Bait, CombatStats, EntityMoved, Fragile, InBackpack, Name, Perishable, Position,
+        ProvidesFood, Renderable, SufferDamage, WantsToThrow, Weight,
+    },
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    noise_system::{NoiseBuilder, THUD_NOISE},
+    particle_system::ParticleBuilder,
+    stats::LevelStats,
+};
+
+// light things fly further
+pub fn throw_range(weight: Option<&Weight>) -> i32 {
+    i32::max(2, 9 - 2 * weight.map_or(1, |w| w.weight))
+}
+
+pub struct ThrowSystem {}
+
+impl<'a> System<'a> for ThrowSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Map>,
+        WriteStorage<'a, WantsToThrow>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, InBackpack>,
+        WriteStorage<'a, Backpack>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Renderable>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Weight>,
+        ReadStorage<'a, Fragile>,
+        ReadStorage<'a, ProvidesFood>,
+        ReadStorage<'a, Perishable>,
+        WriteStorage<'a, Bait>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, EntityMoved>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, NoiseBuilder>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, LevelStats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            map,
+            mut wants_throw,
+            mut positions,
+            mut backpack_items,
+            mut backpacks,
+            names,
+            renderables,
+            combat_stats,
+            weights,
+            fragile,
+            provides_food,
+            perishables,
+            mut baits,
+            mut suffer_damage,
+            mut entity_moved,
+            mut particle_builder,
+            mut noise_builder,
+            mut log,
+            mut level_stats,
+        ) = data;
+
+        let mut gone: Vec<Entity> = Vec::new();
+        for (entity, throw) in (&entities, &wants_throw).join() {
+            let carried = backpack_items
+                .get(throw.item)
+                .map_or(false, |pack| pack.owner == entity);
+            let from = match positions.get(entity) {
+                Some(pos) if carried => Point::new(pos.x, pos.y),
+                _ => continue,
+            };
+            backpack_items.remove(throw.item);
+            if let Some(backpack) = backpacks.get_mut(entity) {
+                backpack.items -= 1;
+            }
+            let thrower = names
+                .get(entity)
+                .map_or("something".to_string(), |name| name.name.clone());
+            let item_name = names
+                .get(throw.item)
+                .map_or("thing".to_string(), |name| name.name.clone());
+            if entity == *player_entity {
+                level_stats.things_thrown += 1;
+            }
+
+            // it flies until it hits a tree or someone gets in the way
+            let (glyph, fg) = renderables.get(throw.item).map_or(
+                (rltk::to_cp437('*'), rltk::RGB::named(rltk::WHITE)),
+                |render| (render.glyph, render.fg),
+            );
+            let mut landing = from;
+            let mut hit = None;
+            for (i, step) in
+                rltk::line2d(rltk::LineAlg::Bresenham, from, Point::new(throw.x, throw.y))
+                    .iter()
+                    .skip(1)
+                    .enumerate()
+            {
+                if step.x < 1 || step.x > map.width - 2 || step.y < 1 || step.y > map.height - 2 {
+                    break;
+                }
+                let idx = map.xy_idx(step.x, step.y);
+                if map.tiles[idx] == TileType::Wall {
+                    break;
+                }
+                landing = *step;
+                particle_builder.request(
+                    step.x,
+                    step.y,
+                    fg,
+                    rltk::RGB::named(rltk::BLACK),
+                    glyph,
+                    60.0 + 40.0 * i as f32,
+                );
+                hit = map.tile_content[idx]
+                    .iter()
+                    .find(|e| **e != entity && combat_stats.contains(**e))
+                    .copied();
+                if hit.is_some() {
+                    break;
+                }
+            }
+            let idx = map.xy_idx(landing.x, landing.y);
+            let seen = map.visible_tiles[idx];
+            noise_builder.request(landing.x, landing.y, THUD_NOISE);
+
+            match hit {
+                Some(target) => {
+                    // not much of a weapon, but it smarts
+                    let damage = weights.get(throw.item).map_or(1, |w| w.weight);
+                    SufferDamage::new_damage(&mut suffer_damage, target, damage);
+                    particle_builder.request(
+                        landing.x,
+                        landing.y,
+                        rltk::RGB::named(rltk::TOMATO),
+                        rltk::RGB::named(rltk::BLACK),
+                        rltk::to_cp437('‼'),
+                        400.0,
+                    );
+                    log.log(LogEntry::Action {
+                        subject: thrower,
+                        verb: format!("hit"),
+                        object: names
+                            .get(target)
+                            .map_or("something".to_string(), |name| name.name.clone()),
+                        suffix: format!("with the {} for {} HP!", item_name, damage),
+                    });
+                }
+                None => {
+                    if entity == *player_entity {
+                        log.log(LogEntry::Action {
+                            subject: thrower,
+                            verb: format!("threw the"),
+                            object: item_name.clone(),
+                            suffix: format!("."),
+                        });
+                    }
+                }
+            }
+
+            if let Some(fragile) = fragile.get(throw.item) {
+                gone.push(throw.item);
+                particle_builder.request(
+                    landing.x,
+                    landing.y,
+                    fg,
+                    rltk::RGB::named(rltk::BLACK),
+                    rltk::to_cp437('*'),
+                    400.0,
+                );
+                if seen {
+                    log.log(LogEntry::Notification {
+                        notification: format!("the {} {}!", item_name, fragile.verb),
+                    });
+                }
+                continue;
+            }
+            if map.tiles[idx] == TileType::Water {
+                gone.push(throw.item);
+                particle_builder.request(
+                    landing.x,
+                    landing.y,
+                    rltk::RGB::named(rltk::LIGHT_BLUE),
+                    rltk::RGB::named(rltk::BLACK),
+                    rltk::to_cp437('~'),
+                    400.0,
+                );
+                if seen {
+                    log.log(LogEntry::Notification {
+                        notification: format!("the {} sinks into the WATER.", item_name),
+                    });
+                }
+                continue;
+            }
+
+            positions
+                .insert(
+                    throw.item,
+                    Position {
+                        x: landing.x,
+                        y: landing.y,
+                    },
+                )
+                .expect("should be able to add position for thrown item");
+            // landing on a trap sets it off
+            let _ = entity_moved.insert(throw.item, EntityMoved {});
+            // anything green and tasty draws the grazers in
+            let is_meat = perishables
+                .get(throw.item)
+                .map_or(false, |perishable| perishable.base_name == "RAW MEAT");
+            if provides_food.contains(throw.item) && !is_meat {
+                let _ = baits.insert(throw.item, Bait {});
+            }
+        }
+
+        wants_throw.clear();
+        for item in gone {
+            entities
+                .delete(item)
+                .expect("should be able to delete broken or sunken item");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn light_things_fly_further() {
+        assert_eq!(throw_range(None), 7);
+        assert_eq!(throw_range(Some(&Weight { weight: 1 })), 7);
+        assert_eq!(throw_range(Some(&Weight { weight: 2 })), 5);
+        assert!(throw_range(Some(&Weight { weight: 0 })) > throw_range(None));
+    }
+
+    #[test]
+    fn anything_can_be_tossed_a_little_way() {
+        assert_eq!(throw_range(Some(&Weight { weight: 4 })), 2);
+        assert_eq!(throw_range(Some(&Weight { weight: 50 })), 2);
+    }
+}
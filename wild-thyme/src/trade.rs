@@ -0,0 +1,161 @@
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+use crate::{
+    components::{Backpack, InBackpack, Name, Vendor, VendorItem, Worth},
+    gamelog::{GameLog, LogEntry},
+    map::Map,
+    random_table::RandomTable,
+    spawn_system::SpawnBuilder,
+    stats::OverallStats,
+};
+
+const STOCK_SIZE: i32 = 4;
+
+// the items offered up for whatever the vendor is asking for
+pub struct BarterOffer {
+    pub items: Vec<Entity>,
+}
+
+impl BarterOffer {
+    pub fn new() -> BarterOffer {
+        BarterOffer { items: Vec::new() }
+    }
+}
+
+fn stock_table(stock_table: &str, map_depth: i32) -> RandomTable {
+    match stock_table {
+        "BLADES" => RandomTable::new()
+            .add("POINTY STICK", 10)
+            .add("ROCK", 6)
+            .add("BARK ARMOR", 4 + map_depth / 2)
+            .add("DART GUN", 2 + map_depth / 2)
+            .add("SMOKER", 3)
+            .add("HATCHET", 4)
+            .add("CAKE KNIFE", map_depth / 3),
+        "PASTRIES" => RandomTable::new()
+            .add("MOSSY MUFFIN", 10)
+            .add("WOBBLY ECLAIR", 6)
+            .add("SEEING SCONE", 4)
+            .add("STARRY STRUDEL", 2)
+            .add("COOL-POUCH", 3),
+        _ => RandomTable::new(),
+    }
+}
+
+fn asking_price(item: &str) -> i32 {
+    match item {
+        "ROCK" => 2,
+        "POINTY STICK" => 6,
+        "BARK ARMOR" => 10,
+        "DART GUN" => 14,
+        "SMOKER" => 8,
+        "HATCHET" => 12,
+        "CAKE KNIFE" => 18,
+        "MOSSY MUFFIN" => 6,
+        "WOBBLY ECLAIR" => 7,
+        "SEEING SCONE" => 9,
+        "STARRY STRUDEL" => 15,
+        "COOL-POUCH" => 12,
+        _ => 10,
+    }
+}
+
+// vendors get new stock once per level, as the level starts
+pub fn restock_vendors(ecs: &mut World) {
+    let depth = ecs.fetch::<Map>().depth;
+    let mut vendors = ecs.write_storage::<Vendor>();
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+    for vendor in (&mut vendors).join() {
+        if vendor.restocked_depth == depth {
+            continue;
+        }
+        let table = stock_table(&vendor.stock_table, depth);
+        vendor.stock.clear();
+        for _i in 0..STOCK_SIZE {
+            let roll = table.roll(&mut rng);
+            if roll == "None" {
+                continue;
+            }
+            vendor.stock.push(VendorItem {
+                price: asking_price(&roll),
+                name: roll,
+            });
+        }
+        vendor.restocked_depth = depth;
+    }
+}
+
+pub fn offer_worth(ecs: &World) -> i32 {
+    let worths = ecs.read_storage::<Worth>();
+    ecs.fetch::<BarterOffer>()
+        .items
+        .iter()
+        .map(|item| worths.get(*item).map(|worth| worth.value).unwrap_or(0))
+        .sum()
+}
+
+pub fn toggle_offer(ecs: &mut World, item: Entity) {
+    let mut offer = ecs.fetch_mut::<BarterOffer>();
+    match offer.items.iter().position(|offered| *offered == item) {
+        Some(i) => {
+            offer.items.remove(i);
+        }
+        None => offer.items.push(item),
+    }
+}
+
+// swaps the offered items for the wanted stock, returns false if the vendor isn't convinced
+pub fn complete_trade(ecs: &mut World, vendor: Entity, wanted: usize) -> bool {
+    let wanted_item = match ecs
+        .read_storage::<Vendor>()
+        .get(vendor)
+        .and_then(|vendor| vendor.stock.get(wanted))
+    {
+        Some(wanted_item) => wanted_item.clone(),
+        None => return false,
+    };
+    let vendor_name = ecs
+        .read_storage::<Name>()
+        .get(vendor)
+        .map(|name| name.name.clone())
+        .unwrap_or_default();
+    if offer_worth(ecs) < wanted_item.price {
+        ecs.fetch_mut::<GameLog>().log(LogEntry::Quip {
+            subject: vendor_name,
+            quip: format!("the {} is worth more than that!", wanted_item.name),
+        });
+        return false;
+    }
+
+    let offered: Vec<Entity> = ecs.fetch_mut::<BarterOffer>().items.drain(..).collect();
+    {
+        let player_entity = *ecs.fetch::<Entity>();
+        let backpack_items = ecs.read_storage::<InBackpack>();
+        let mut backpacks = ecs.write_storage::<Backpack>();
+        if let Some(backpack) = backpacks.get_mut(player_entity) {
+            backpack.items -= offered
+                .iter()
+                .filter(|item| backpack_items.get(**item).is_some())
+                .count() as i32;
+        }
+    }
+    for item in offered.iter() {
+        ecs.delete_entity(*item)
+            .expect("should be able to delete bartered item");
+    }
+    if let Some(vendor) = ecs.write_storage::<Vendor>().get_mut(vendor) {
+        vendor.stock.remove(wanted);
+    }
+    let player_pos = *ecs.fetch::<Point>();
+    ecs.fetch_mut::<SpawnBuilder>()
+        .request(player_pos.x, player_pos.y, wanted_item.name.clone());
+    let player_name = ecs.fetch::<OverallStats>().name.clone();
+    ecs.fetch_mut::<GameLog>().log(LogEntry::Action {
+        subject: player_name,
+        verb: format!("trades {} items with", offered.len()),
+        object: vendor_name,
+        suffix: format!("for a {}! [SPACE] to pick it up", wanted_item.name),
+    });
+    true
+}
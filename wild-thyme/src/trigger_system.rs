@@ -5,11 +5,12 @@ use specs::prelude::*;
 
 use crate::{
     components::{
-        Confusion, EntityMoved, EntryTrigger, Hidden, InflictsDamage, Name, Position,
-        SingleActivation, SpawnsMobs, SufferDamage, VisibleToPlayer,
+        EntityMoved, EntryTrigger, Hidden, InflictsDamage, InflictsStatus, Name, Position,
+        SingleActivation, SpawnsMobs, StatusEffects, SufferDamage, VisibleToPlayer,
     },
     gamelog::{GameLog, LogEntry},
     map::Map,
+    noise_system::{NoiseBuilder, NEST_NOISE},
     particle_system::ParticleBuilder,
     spawn_system::SpawnBuilder,
     stats::LevelStats,
@@ -31,13 +32,15 @@ impl<'a> System<'a> for TriggerSystem {
         WriteStorage<'a, SufferDamage>,
         WriteExpect<'a, ParticleBuilder>,
         ReadStorage<'a, SingleActivation>,
-        WriteStorage<'a, Confusion>,
+        ReadStorage<'a, InflictsStatus>,
+        WriteStorage<'a, StatusEffects>,
         ReadStorage<'a, SpawnsMobs>,
         WriteExpect<'a, SpawnBuilder>,
         WriteExpect<'a, RandomNumberGenerator>,
         WriteExpect<'a, LevelStats>,
         ReadExpect<'a, Entity>,
         ReadStorage<'a, VisibleToPlayer>,
+        WriteExpect<'a, NoiseBuilder>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -54,13 +57,15 @@ impl<'a> System<'a> for TriggerSystem {
             mut suffer_damage,
             mut particle_builder,
             single_activation,
-            mut confusion,
+            inflicts_status,
+            mut statuses,
             spawns_mobs,
             mut spawn_builder,
             mut rng,
             mut level_stats,
             player,
             visible_to_player,
+            mut noise_builder,
         ) = data;
 
         let mut remove_entities: Vec<Entity> = Vec::new();
@@ -82,7 +87,12 @@ impl<'a> System<'a> for TriggerSystem {
                     } else {
                         log_suffix = "somewhere, beyond the trees..."
                     }
-                    if let Some(name) = names.get(*triggered_entity) {
+                    // things that go off every time only bother telling the player about the player
+                    let repeats = single_activation.get(*triggered_entity).is_none();
+                    if let Some(name) = names
+                        .get(*triggered_entity)
+                        .filter(|_| !repeats || triggering_entity == *player)
+                    {
                         log.log(LogEntry::Action {
                             subject: format!("{}", &name.name),
                             verb: format!("{}", &trigger.verb),
@@ -110,26 +120,41 @@ impl<'a> System<'a> for TriggerSystem {
                         );
                     }
 
-                    // inflict confusion if needed
-                    let mut turns = 0;
-                    if let Some(confused) = confusion.get(*triggered_entity) {
-                        turns = confused.turns;
-                    }
-                    if turns > 0 {
+                    // inflict a status if needed
+                    if let Some(inflicts) = inflicts_status.get(*triggered_entity) {
                         particle_builder.request(
                             pos.x,
                             pos.y,
                             rltk::RGB::named(rltk::ORANGE),
                             rltk::RGB::named(rltk::BLACK),
-                            rltk::to_cp437('?'),
+                            rltk::to_cp437(inflicts.kind.glyph()),
                             200.0,
                         );
 
-                        Confusion::new_confusion(&mut confusion, triggering_entity, turns);
+                        StatusEffects::inflict(
+                            &mut statuses,
+                            triggering_entity,
+                            inflicts.kind,
+                            inflicts.turns,
+                        );
+                        if triggering_entity == *player {
+                            log.log(LogEntry::Alert {
+                                alert: format!(
+                                    "{} is {}!",
+                                    names
+                                        .get(triggering_entity)
+                                        .expect("player should always have Name")
+                                        .name,
+                                    inflicts.kind.name()
+                                ),
+                            });
+                        }
                     }
 
                     // spawn things if needed
                     if let Some(spawns_mobs) = spawns_mobs.get(*triggered_entity) {
+                        // a nest breaking open makes a right racket
+                        noise_builder.request(pos.x, pos.y, NEST_NOISE);
                         particle_builder.request(
                             pos.x,
                             pos.y,
@@ -1,5 +1,8 @@
 use crate::{
-    components::{Hidden, Name, VisibleToPlayer},
+    components::{
+        Equipped, Hidden, InBackpack, LightSource, Name, StatusEffects, StatusKind, VisibleToPlayer,
+    },
+    day_night_system::WorldClock,
     gamelog::{GameLog, LogEntry},
 };
 
@@ -7,6 +10,10 @@ use super::{Map, Player, Position, Viewshed};
 use rltk::{field_of_view, Point};
 use specs::prelude::*;
 
+const FOG_DIM_DISTANCE: f32 = 4.0;
+// lights further off than a clear day's sight are never seen
+const DAY_SIGHT: i32 = 12;
+
 pub struct VisibilitySystem {}
 
 impl<'a> System<'a> for VisibilitySystem {
@@ -21,6 +28,11 @@ impl<'a> System<'a> for VisibilitySystem {
         WriteExpect<'a, GameLog>,
         ReadStorage<'a, Name>,
         WriteStorage<'a, VisibleToPlayer>,
+        ReadExpect<'a, WorldClock>,
+        ReadStorage<'a, LightSource>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, StatusEffects>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -28,25 +40,120 @@ impl<'a> System<'a> for VisibilitySystem {
             mut map,
             entities,
             mut viewshed,
-            pos,
+            positions,
             player,
             mut hidden,
             mut rng,
             mut log,
             names,
             mut visible_to_player,
+            clock,
+            lights,
+            backpack_items,
+            equipped,
+            statuses,
         ) = data;
 
-        for (ent, viewshed, pos) in (&entities, &mut viewshed, &pos).join() {
+        let glowing: Vec<(Entity, Point)> = (&entities, &statuses, &positions)
+            .join()
+            .filter(|(_, status, _)| status.has(StatusKind::Glowing))
+            .map(|(e, _, pos)| (e, Point::new(pos.x, pos.y)))
+            .collect();
+        // glowing things wander about, so keep the player's view of them fresh
+        if glowing.iter().any(|(e, _)| player.get(*e).is_none()) {
+            for (_player, viewshed) in (&player, &mut viewshed).join() {
+                viewshed.dirty = true;
+            }
+        }
+
+        for (ent, viewshed, pos) in (&entities, &mut viewshed, &positions).join() {
             if viewshed.dirty {
                 viewshed.dirty = false;
                 viewshed.visible_tiles.clear();
-                viewshed.visible_tiles =
-                    field_of_view(Point::new(pos.x, pos.y), viewshed.range, &*map);
+                let mut range = viewshed.range;
+                // nearby fog makes it hard to see far
+                if player.get(ent).is_some()
+                    && map.fog_tiles.iter().any(|idx| {
+                        let fog = Point::new(*idx as i32 % map.width, *idx as i32 / map.width);
+                        rltk::DistanceAlg::Pythagoras.distance2d(fog, Point::new(pos.x, pos.y))
+                            < FOG_DIM_DISTANCE
+                    })
+                {
+                    range = i32::max(2, range / 2);
+                }
+                // a carried light lets the player see further in the dark
+                let mut carried_light = 0;
+                if player.get(ent).is_some() {
+                    for (light, light_ent) in (&lights, &entities).join() {
+                        let carried = backpack_items
+                            .get(light_ent)
+                            .map_or(false, |pack| pack.owner == ent)
+                            || equipped.get(light_ent).map_or(false, |eq| eq.owner == ent);
+                        if carried {
+                            carried_light = i32::max(carried_light, light.radius);
+                        }
+                    }
+                    range = i32::max(range, carried_light);
+                }
+                let player_pos = Point::new(pos.x, pos.y);
+                viewshed.visible_tiles = field_of_view(player_pos, range, &*map);
                 viewshed
                     .visible_tiles
                     .retain(|p| p.x >= 0 && p.x < map.width && p.y >= 0 && p.y < map.height);
 
+                // anything glowing stands out from a long way off, day or night
+                let mut glow_line = None;
+                for (glower, glow_pos) in glowing.iter() {
+                    if *glower == ent
+                        || viewshed.visible_tiles.contains(glow_pos)
+                        || rltk::DistanceAlg::Pythagoras.distance2d(*glow_pos, player_pos)
+                            > DAY_SIGHT as f32
+                    {
+                        continue;
+                    }
+                    let glow_line = glow_line
+                        .get_or_insert_with(|| field_of_view(player_pos, DAY_SIGHT, &*map));
+                    if glow_line.contains(glow_pos) {
+                        viewshed.visible_tiles.push(*glow_pos);
+                    }
+                }
+
+                if player.get(ent).is_some() {
+                    map.lit_tiles.clear();
+                    for tile in viewshed.visible_tiles.iter() {
+                        if rltk::DistanceAlg::Pythagoras.distance2d(*tile, player_pos)
+                            <= carried_light as f32
+                        {
+                            let idx = map.xy_idx(tile.x, tile.y);
+                            map.lit_tiles.insert(idx);
+                        }
+                    }
+                    // tiles lit by lights out in the world can be seen from afar
+                    if clock.max_sight() < DAY_SIGHT {
+                        let mut sight_line = None;
+                        for (light, light_pos) in (&lights, &positions).join() {
+                            let light_point = Point::new(light_pos.x, light_pos.y);
+                            if rltk::DistanceAlg::Pythagoras.distance2d(light_point, player_pos)
+                                > (DAY_SIGHT + light.radius) as f32
+                            {
+                                continue;
+                            }
+                            let sight_line = sight_line
+                                .get_or_insert_with(|| field_of_view(player_pos, DAY_SIGHT, &*map));
+                            for tile in field_of_view(light_point, light.radius, &*map) {
+                                if !sight_line.contains(&tile) {
+                                    continue;
+                                }
+                                let idx = map.xy_idx(tile.x, tile.y);
+                                map.lit_tiles.insert(idx);
+                                if !viewshed.visible_tiles.contains(&tile) {
+                                    viewshed.visible_tiles.push(tile);
+                                }
+                            }
+                        }
+                    }
+                }
+
                 if let Some(_p) = player.get(ent) {
                     for t in map.visible_tiles.iter_mut() {
                         *t = false
@@ -63,7 +170,14 @@ impl<'a> System<'a> for VisibilitySystem {
                                 if rng.roll_dice(1, 24) == 1 {
                                     if let Some(name) = names.get(*e) {
                                         log.log(LogEntry::Alert {
-                                            alert: format!("YOU spotted a {}!", &name.name),
+                                            alert: format!(
+                                                "{} spotted a {}!",
+                                                names
+                                                    .get(ent)
+                                                    .expect("player should always have Name")
+                                                    .name,
+                                                &name.name
+                                            ),
                                         });
                                         let _ = visible_to_player.insert(*e, VisibleToPlayer {});
                                     }
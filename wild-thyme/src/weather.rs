@@ -0,0 +1,194 @@
+use rltk::{Point, RandomNumberGenerator, RGB};
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+use crate::{
+    fog,
+    gamelog::{GameLog, LogEntry},
+    map::{Map, TileType},
+    particle_system::ParticleBuilder,
+    stats::{LevelStats, OverallStats},
+    RunState,
+};
+
+const MAX_PUDDLES: i32 = 8;
+const PUDDLE_CHANCE: i32 = 12;
+const OVERLAY_PARTICLES: i32 = 6;
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize, Default)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Fog,
+    Wind,
+    ColdSnap,
+}
+
+impl Weather {
+    pub fn name(&self) -> &str {
+        match self {
+            Weather::Clear => "CLEAR",
+            Weather::Rain => "RAIN",
+            Weather::Fog => "FOG",
+            Weather::Wind => "WIND",
+            Weather::ColdSnap => "COLD SNAP",
+        }
+    }
+
+    fn forecast(&self, player_name: &str) -> String {
+        match self {
+            Weather::Clear => "the sky is clear over this trail.".to_string(),
+            Weather::Rain => "rain patters down through the leaves..".to_string(),
+            Weather::Fog => "a thick fog hangs low over this trail..".to_string(),
+            Weather::Wind => "a strong wind howls through the trees..".to_string(),
+            Weather::ColdSnap => format!(
+                "a bitter cold bites at {}'s fingers. something warm to wear would help..",
+                player_name
+            ),
+        }
+    }
+}
+
+// deeper trails get nastier weather
+pub fn roll_weather(depth: i32, rng: &mut RandomNumberGenerator) -> Weather {
+    let roll = rng.roll_dice(1, 12 + depth / 2);
+    match roll {
+        1..=5 => Weather::Clear,
+        6..=7 => Weather::Rain,
+        8 => Weather::Fog,
+        9..=10 => Weather::Wind,
+        _ => Weather::ColdSnap,
+    }
+}
+
+pub fn start_weather(ecs: &mut World) {
+    let weather = ecs.fetch::<Map>().weather;
+    ecs.fetch_mut::<LevelStats>().weather = weather.name().to_string();
+    if weather == Weather::Clear {
+        return;
+    }
+    let forecast = weather.forecast(&ecs.fetch::<OverallStats>().name);
+    ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+        notification: forecast,
+    });
+    if weather == Weather::Fog {
+        fog::spawn_fog_bank(ecs);
+        fog::spawn_fog_bank(ecs);
+    }
+}
+
+// a gust might carry a thrown item off course
+pub fn wind_drift(ecs: &World, target: Point) -> Point {
+    let map = ecs.fetch::<Map>();
+    if map.weather != Weather::Wind {
+        return target;
+    }
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+    if rng.roll_dice(1, 2) == 1 {
+        return target;
+    }
+    let drifted = Point::new(
+        i32::min(map.width - 2, i32::max(1, target.x + rng.range(-2, 3))),
+        i32::min(map.height - 2, i32::max(1, target.y + rng.range(-1, 2))),
+    );
+    if drifted != target {
+        ecs.fetch_mut::<GameLog>().log(LogEntry::Notification {
+            notification: "the wind carries it off course!".to_string(),
+        });
+    }
+    drifted
+}
+
+pub struct WeatherSystem {}
+
+impl<'a> System<'a> for WeatherSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Point>,
+        WriteExpect<'a, ParticleBuilder>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, LevelStats>,
+        WriteExpect<'a, GameLog>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            mut map,
+            runstate,
+            player_pos,
+            mut particle_builder,
+            mut rng,
+            mut level_stats,
+            mut log,
+        ) = data;
+
+        if *runstate != RunState::CoreMonsterTurn {
+            return;
+        }
+
+        // draw a little weather over what the player can see
+        let (glyph, color) = match map.weather {
+            Weather::Clear | Weather::Fog => return,
+            Weather::Rain => ('|', "#6080c0"),
+            Weather::Wind => ('~', "#a0a0a0"),
+            Weather::ColdSnap => ('*', "#e0f0ff"),
+        };
+        for _i in 0..OVERLAY_PARTICLES {
+            let x = player_pos.x + rng.range(-8, 9);
+            let y = player_pos.y + rng.range(-8, 9);
+            if x < 0 || x > map.width - 1 || y < 0 || y > map.height - 1 {
+                continue;
+            }
+            if !map.visible_tiles[map.xy_idx(x, y)] {
+                continue;
+            }
+            particle_builder.request(
+                x,
+                y,
+                RGB::from_hex(color).expect("hardcoded"),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437(glyph),
+                150.0,
+            );
+        }
+
+        // rain pools up in open clearings
+        if map.weather != Weather::Rain
+            || level_stats.puddles_formed >= MAX_PUDDLES
+            || rng.roll_dice(1, PUDDLE_CHANCE) != 1
+        {
+            return;
+        }
+        let x = rng.range(2, map.width - 2);
+        let y = rng.range(2, map.height - 2);
+        let near_player =
+            rltk::DistanceAlg::Pythagoras.distance2d(Point::new(x, y), *player_pos) < 2.0;
+        if near_player || !is_open_clearing(&map, x, y) {
+            return;
+        }
+        let idx = map.xy_idx(x, y);
+        map.tiles[idx] = TileType::Water;
+        map.blocked[idx] = true;
+        level_stats.puddles_formed += 1;
+        if map.visible_tiles[idx] {
+            log.log(LogEntry::Notification {
+                notification: "the rain pools into a puddle.".to_string(),
+            });
+        }
+    }
+}
+
+// puddles only form where they won't close off a path
+fn is_open_clearing(map: &Map, x: i32, y: i32) -> bool {
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let idx = map.xy_idx(x + dx, y + dy);
+            if map.tiles[idx] != TileType::Floor || !map.tile_content[idx].is_empty() {
+                return false;
+            }
+        }
+    }
+    true
+}
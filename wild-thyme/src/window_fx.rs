@@ -1,4 +1,7 @@
-use crate::stats::{LevelStats, OverallStats};
+use crate::{
+    name_mangler::{fill_name, NAME_PLACEHOLDER},
+    stats::{LevelStats, OverallStats},
+};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(js_namespace = windowfx)]
@@ -15,6 +18,7 @@ extern "C" {
         portals_taken: i32,
         steps_taken: i32,
         level_stats: JsValue,
+        player_name: String,
     );
     fn player_died(
         deepest_level: i32,
@@ -26,6 +30,7 @@ extern "C" {
         portals_taken: i32,
         steps_taken: i32,
         level_stats: JsValue,
+        player_name: String,
     );
     fn player_won(
         deepest_level: i32,
@@ -44,9 +49,30 @@ extern "C" {
         hot_points: i32,
         mold_points: i32,
         edible_points: i32,
+        player_name: String,
     );
 }
 
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_namespace = windowfx)]
+extern "C" {
+    #[wasm_bindgen(js_name = player_name)]
+    fn web_player_name() -> String;
+    #[wasm_bindgen(js_name = converse)]
+    fn web_converse(
+        npc_name: String,
+        persona: String,
+        depth: i32,
+        recent_events: JsValue,
+        player_line: String,
+        misremembered_name: String,
+    );
+    #[wasm_bindgen(js_name = npc_reply)]
+    fn web_npc_reply() -> Option<String>;
+    #[wasm_bindgen(js_name = remember_name)]
+    fn web_remember_name(misremembered_name: String);
+}
+
 pub fn warp_effect() {
     #[allow(unused_unsafe)]
     unsafe {
@@ -61,9 +87,89 @@ pub fn nudge_effect() {
     }
 }
 
+#[cfg(target_arch = "wasm32")]
+pub fn player_name() -> String {
+    #[allow(unused_unsafe)]
+    unsafe {
+        web_player_name()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn player_name() -> String {
+    String::new()
+}
+
+// the narrator is the druid, who never quite gets the name right
+fn narrator_name(stats: &OverallStats) -> String {
+    fill_name(NAME_PLACEHOLDER, stats, "FOREST DRUID")
+}
+
+// good-thyme picks this up from the game stats sent along with every narration
+#[cfg(target_arch = "wasm32")]
+fn remember_name(misremembered_name: String) {
+    #[allow(unused_unsafe)]
+    unsafe {
+        web_remember_name(misremembered_name)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn remember_name(_misremembered_name: String) {}
+
+#[cfg(target_arch = "wasm32")]
+pub fn converse(
+    npc_name: String,
+    persona: String,
+    depth: i32,
+    recent_events: Vec<String>,
+    player_line: String,
+    misremembered_name: String,
+) {
+    let serialized_recent_events = serde_wasm_bindgen::to_value(&recent_events)
+        .expect("recent events should all be serializable");
+    #[allow(unused_unsafe)]
+    unsafe {
+        web_converse(
+            npc_name,
+            persona,
+            depth,
+            serialized_recent_events,
+            player_line,
+            misremembered_name,
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn converse(
+    _npc_name: String,
+    _persona: String,
+    _depth: i32,
+    _recent_events: Vec<String>,
+    _player_line: String,
+    _misremembered_name: String,
+) {
+}
+
+// None while good-thyme is still thinking, empty if it couldn't answer
+#[cfg(target_arch = "wasm32")]
+pub fn npc_reply() -> Option<String> {
+    #[allow(unused_unsafe)]
+    unsafe {
+        web_npc_reply()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn npc_reply() -> Option<String> {
+    Some(String::new())
+}
+
 pub fn narrate(stats: &OverallStats, level_stats: &LevelStats) {
     let serialized_level_stats =
         serde_wasm_bindgen::to_value(&level_stats).expect("level stats should all be serializable");
+    remember_name(narrator_name(stats));
     #[allow(unused_unsafe)]
     unsafe {
         update_stats(
@@ -76,6 +182,7 @@ pub fn narrate(stats: &OverallStats, level_stats: &LevelStats) {
             stats.portals_taken,
             stats.steps_taken,
             serialized_level_stats,
+            stats.name.clone(),
         );
     }
 }
@@ -83,6 +190,7 @@ pub fn narrate(stats: &OverallStats, level_stats: &LevelStats) {
 pub fn player_died_effect(stats: &OverallStats, level_stats: &LevelStats) {
     let serialized_level_stats =
         serde_wasm_bindgen::to_value(&level_stats).expect("level stats should all be serializable");
+    remember_name(narrator_name(stats));
     #[allow(unused_unsafe)]
     unsafe {
         player_died(
@@ -95,11 +203,13 @@ pub fn player_died_effect(stats: &OverallStats, level_stats: &LevelStats) {
             stats.portals_taken,
             stats.steps_taken,
             serialized_level_stats,
+            stats.name.clone(),
         );
     }
 }
 
 pub fn player_won_effect(stats: &OverallStats) {
+    remember_name(narrator_name(stats));
     #[allow(unused_unsafe)]
     unsafe {
         player_won(
@@ -119,6 +229,7 @@ pub fn player_won_effect(stats: &OverallStats) {
             stats.cake.hot_points,
             stats.cake.mold_points,
             stats.cake.edible_points,
+            stats.name.clone(),
         );
     }
 }